@@ -0,0 +1,59 @@
+/// The case conventions supported by `#[response(rename_all = "...")]`.
+pub const RULES: &[&str] = &[
+    "lowercase",
+    "UPPERCASE",
+    "PascalCase",
+    "camelCase",
+    "snake_case",
+    "SCREAMING_SNAKE_CASE",
+    "kebab-case",
+    "SCREAMING-KEBAB-CASE",
+];
+
+/// Converts a PascalCase variant name according to `rule`, which must be one of `RULES`.
+pub fn rename(name: &str, rule: &str) -> String {
+    let words = words(name);
+    match rule {
+        "lowercase" => name.to_lowercase(),
+        "UPPERCASE" => name.to_uppercase(),
+        "PascalCase" => name.to_string(),
+        "camelCase" => {
+            let mut chars = name.chars();
+            match chars.next() {
+                Some(first) => first.to_lowercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+        "snake_case" => words.join("_").to_lowercase(),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-").to_lowercase(),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        _ => unreachable!(),
+    }
+}
+
+/// Splits a PascalCase name into its words, keeping acronyms like `HTTP` in `HTTPError` together.
+fn words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        let boundary = i > 0
+            && (c == '_'
+                || c.is_uppercase()
+                    && (chars[i - 1].is_lowercase()
+                        || chars[i - 1].is_numeric()
+                        || chars.get(i + 1).is_some_and(|next| next.is_lowercase())));
+        if boundary && !word.is_empty() {
+            words.push(word);
+            word = String::new();
+        }
+        if c != '_' {
+            word.push(c);
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
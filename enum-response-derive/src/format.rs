@@ -0,0 +1,85 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A field referenced from a format string, e.g. `{id}` or `{0}`.
+#[derive(Debug, PartialEq)]
+pub enum FormatArg {
+    Named(String),
+    Index(usize),
+}
+
+impl FormatArg {
+    /// The name of the named argument the placeholder is rewritten to.
+    pub fn binding(&self) -> String {
+        match *self {
+            FormatArg::Named(ref name) => name.clone(),
+            FormatArg::Index(ix) => format!("_{}", ix),
+        }
+    }
+}
+
+/// A thiserror-style format string, with positional placeholders rewritten into named ones
+/// (`{0}` becomes `{_0}`) so that all fields can be passed as named arguments to `format!`.
+pub struct Format {
+    pub format: String,
+    pub args: Vec<FormatArg>,
+}
+
+pub fn parse(input: &str) -> Result<Format, String> {
+    let mut format = String::with_capacity(input.len());
+    let mut args = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '{' => {
+                let placeholder = read_placeholder(&mut chars)?;
+                let (name, spec) = match placeholder.find(':') {
+                    Some(ix) => placeholder.split_at(ix),
+                    None => (placeholder.as_str(), ""),
+                };
+                let name = name.trim();
+                let arg = if name.is_empty() {
+                    return Err(String::from(
+                        "format placeholders must name a field, e.g. `{0}` or `{name}`",
+                    ));
+                } else if let Ok(ix) = name.parse::<usize>() {
+                    FormatArg::Index(ix)
+                } else {
+                    FormatArg::Named(name.to_string())
+                };
+
+                format.push('{');
+                format.push_str(&arg.binding());
+                format.push_str(spec);
+                format.push('}');
+                if !args.contains(&arg) {
+                    args.push(arg);
+                }
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '}' => return Err(String::from("unmatched `}` in format string")),
+            c => format.push(c),
+        }
+    }
+
+    Ok(Format { format, args })
+}
+
+fn read_placeholder(chars: &mut Peekable<Chars>) -> Result<String, String> {
+    let mut placeholder = String::new();
+    for c in chars {
+        if c == '}' {
+            return Ok(placeholder);
+        }
+        placeholder.push(c);
+    }
+    Err(String::from("unterminated `{` in format string"))
+}
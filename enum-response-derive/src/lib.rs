@@ -1,18 +1,28 @@
-#![feature(proc_macro)]
+#![recursion_limit = "256"]
 
 extern crate enum_response;
 extern crate proc_macro;
 extern crate proc_macro2;
 #[macro_use]
 extern crate quote;
+#[macro_use]
 extern crate syn;
 
+mod case;
+mod format;
+
+use std::fmt::Display;
 use std::str::FromStr;
 
+use format::FormatArg;
 use proc_macro::TokenStream;
-use proc_macro2::{TokenStream as Tokens, Span};
-use syn::{Data, DataEnum, Fields, Ident, Lit, Meta, MetaList, MetaNameValue, NestedMeta};
+use proc_macro2::{Span, TokenStream as Tokens};
 use quote::TokenStreamExt;
+use syn::spanned::Spanned;
+use syn::{
+    Attribute, Data, DataEnum, DataStruct, Fields, Ident, Lit, LitStr, Meta, MetaList,
+    MetaNameValue, NestedMeta,
+};
 
 #[proc_macro_derive(EnumResponse, attributes(response))]
 pub fn enum_from(input: TokenStream) -> TokenStream {
@@ -23,300 +33,530 @@ pub fn enum_from(input: TokenStream) -> TokenStream {
     derive(&ast)
 }
 
+#[derive(Clone)]
 enum ValueSource {
     Number(u16),
-    String(String),
-    TupleField(usize),
-    StructField(String),
+    String(LitStr),
+    Constant(Ident),
+    TupleField(usize, Span),
+    StructField(String, Span),
 }
 
-fn derive(ast: &syn::DeriveInput) -> TokenStream {
-    let enum_name = &ast.ident;
-    let variants = match ast.data {
-        Data::Enum(DataEnum { ref variants, .. }) => variants,
-        _ => panic!(
-            "#[derive(EnumResponse)] can only be applied to enums. {} is not an enum.",
-            enum_name
-        ),
-    };
+/// The `#[response(...)]` attributes of a single variant, or of the enum itself.
+#[derive(Default)]
+struct ResponseAttrs {
+    status: Option<ValueSource>,
+    reason: Option<ValueSource>,
+    problem_type: Option<ValueSource>,
+    content_type: Option<ValueSource>,
+    code: Option<ValueSource>,
+    title: Option<ValueSource>,
+    detail: Option<ValueSource>,
+    body: Option<ValueSource>,
+    /// The field to delegate to, or `None` for `transparent` without a value.
+    transparent: Option<(Span, Option<ValueSource>)>,
+    default_status: Option<ValueSource>,
+    default_reason: Option<ValueSource>,
+    deny_fallback: Option<Span>,
+    integrations: Integrations,
+    rename_all: Option<String>,
+    headers: Vec<Header>,
+    retry_after: Option<ValueSource>,
+    challenges: Vec<Challenge>,
+    allow_missing_challenge: bool,
+    location: Option<ValueSource>,
+}
 
-    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
-    let mut status_patterns = Vec::new();
-    let mut reason_patterns = Vec::new();
+/// The web framework traits to implement for the enum or struct, e.g. with
+/// `#[response(axum)]`.
+#[derive(Clone, Copy, Default)]
+struct Integrations {
+    actix: bool,
+    axum: bool,
+    rocket: bool,
+    warp: bool,
+}
 
-    for variant in variants {
-        let variant_name = &variant.ident;
+/// A `#[response(header(name = "...", value = "..."))]` attribute. The value is either a string
+/// literal or selected with `value_field`.
+struct Header {
+    name: LitStr,
+    value: ValueSource,
+}
 
-        let mut status = None;
-        let mut reason = None;
+/// A `#[response(challenge(scheme = "...", ...))]` attribute. All keys besides `scheme` are
+/// challenge parameters, either given as string literal or, with a `_field` suffix, selecting a
+/// field.
+struct Challenge {
+    scheme: String,
+    params: Vec<(String, ValueSource)>,
+}
 
-        for attr in &variant.attrs {
-            // TODO: unwrap
-            match attr.interpret_meta().unwrap() {
-                Meta::List(MetaList {
-                    ref ident,
-                    ref nested,
-                    ..
-                }) if ident == "response" =>
-                {
-                    // TODO: check paren_token?
-                    for item in nested {
-                        let (name, val) = match *item {
-                            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
-                                ref ident,
-                                ref lit,
-                                ..
-                            })) => {
-                                // TODO: check eq_token?
-                                (ident, lit)
-                            }
-                            NestedMeta::Meta(Meta::Word(ref name)) => {
-                                panic!("unknown response field attribute `{}`", name)
-                            }
-                            NestedMeta::Meta(Meta::List(MetaList { ref ident, .. })) => {
-                                panic!("unknown response field attribute `{}`", ident)
-                            }
-                            NestedMeta::Literal(_) => {
-                                panic!(
-                                    "unexpected literal in response field attribute of `{}`",
-                                    variant_name
-                                );
-                            }
-                        };
-
-                        match name.to_string().as_str() {
-                            "status" => {
-                                status = Some(match val {
-                                    Lit::Int(ref status) => ValueSource::Number(status.value() as u16),
-                                    Lit::Str(ref name) => {
-                                        let name = name.value();
-                                        if let Ok(status) = u16::from_str(name.as_str()) {
-                                            ValueSource::Number(status)
-                                        } else {
-                                            ValueSource::String(name)
-                                        }
-                                    }
-                                    _ => {
-                                        panic!(
-                                            "response status attribute value must be \
-                                             of type int or string"
-                                        );
-                                    }
-                                });
-                            }
-                            "status_field" => {
-                                match val {
-                                    Lit::Int(ref ix) => {
-                                        status = Some(ValueSource::TupleField(ix.value() as usize));
-                                    }
-                                    Lit::Str(ref s) => {
-                                        let s = s.value();
-                                        status = Some(match usize::from_str(&s) {
-                                            Ok(ix) => ValueSource::TupleField(ix as usize),
-                                            Err(_) => ValueSource::StructField(s),
-                                        });
-                                    }
-                                    _ => {
-                                        panic!(
-                                            "response status attribute value must be \
-                                             of type int or string"
-                                        );
-                                    }
-                                };
-                            }
-                            "reason" => {
-                                match val {
-                                    Lit::Str(ref s) => {
-                                        reason = Some(ValueSource::String(s.value()));
-                                    }
-                                    _ => {
-                                        panic!(
-                                            "response reason attribute value must be \
-                                             of type string"
-                                        );
-                                    }
-                                };
-                            }
-                            "reason_field" => {
-                                match val {
-                                    Lit::Int(ref ix) => {
-                                        reason = Some(ValueSource::TupleField(ix.value() as usize));
-                                    }
-                                    Lit::Str(ref s) => {
-                                        let s = s.value();
-                                        reason = Some(match usize::from_str(&s) {
-                                            Ok(ix) => ValueSource::TupleField(ix as usize),
-                                            Err(_) => ValueSource::StructField(s),
-                                        });
-                                    }
-                                    _ => {
-                                        panic!(
-                                            "response reason attribute value must be \
-                                             of type int or string"
-                                        );
-                                    }
-                                };
-                            }
-                            _ => panic!("unknown response field attribute `{}`", name),
-                        }
-                    }
-                }
-                _ => continue,
-            }
-        }
+/// Attributes that can only be used on the enum itself.
+const ENUM_KEYS: &[&str] = &[
+    "default_status",
+    "default_reason",
+    "deny_fallback",
+    "rename_all",
+];
 
-        // TODO handling of status and reason are redundant
+/// Attributes that can only be used on the enum or struct itself, but not on variants.
+const TYPE_KEYS: &[&str] = &["actix", "axum", "rocket", "warp"];
 
-        match status {
-            Some(ValueSource::Number(status)) => {
-                let pattern = variant_pattern(enum_name, variant_name, &variant.fields);
-                // make sure it is a valid status code
-                ::enum_response::StatusCode::from_u16(status).unwrap();
-                status_patterns.push(quote!{
-                    #pattern => ::enum_response::StatusCode::from_u16(#status).unwrap(),
-                });
-            }
-            Some(ValueSource::String(ref status)) => {
-                let pattern = variant_pattern(enum_name, variant_name, &variant.fields);
-                let status = Ident::new(status.as_str(), Span::call_site());
-                status_patterns.push(quote!{
-                    #pattern => ::enum_response::StatusCode::#status,
-                });
+/// Attributes that can be used on the enum as well as on its variants.
+const SHARED_KEYS: &[&str] = &["content_type"];
+
+/// The item `#[response(...)]` attributes are parsed for.
+#[derive(Clone, Copy, PartialEq)]
+enum Target {
+    Enum,
+    Variant,
+    Struct,
+}
+
+/// Collects all errors found while deriving, so that they can be reported together.
+#[derive(Default)]
+struct Errors(Vec<(Span, String)>);
+
+impl Errors {
+    fn push<T: Display>(&mut self, span: Span, msg: T) {
+        self.0.push((span, msg.to_string()));
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn into_tokens(self) -> Tokens {
+        let mut tokens = Tokens::new();
+        tokens.append_all(self.0.into_iter().map(|(span, msg)| {
+            quote_spanned! {span=>
+                compile_error!(#msg);
             }
-            Some(ValueSource::TupleField(ix)) => {
-                let fields = match variant.fields {
-                    Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) => unnamed,
-                    _ => panic!("status index only works for tuple variants"),
-                };
+        }));
+        tokens
+    }
+}
 
-                if fields.iter().nth(ix).is_none() {
-                    panic!(
-                        "[error(status_field = {})]: No tuple field at {} found for {}",
-                        ix, ix, variant_name
-                    );
+/// An enum variant, or the struct itself, whose response is derived.
+struct Variant<'a> {
+    ident: &'a Ident,
+    /// The path used to match the variant, e.g. `Error::NotFound` or `RateLimited`.
+    path: Tokens,
+    fields: &'a Fields,
+    attrs: &'a [Attribute],
+    /// Either `variant` or `struct`, used in error messages.
+    kind: &'static str,
+}
+
+fn derive(ast: &syn::DeriveInput) -> TokenStream {
+    let name = &ast.ident;
+    let variants = match ast.data {
+        Data::Enum(DataEnum { ref variants, .. }) => variants
+            .iter()
+            .map(|variant| {
+                let variant_name = &variant.ident;
+                Variant {
+                    ident: variant_name,
+                    path: quote!(#name::#variant_name),
+                    fields: &variant.fields,
+                    attrs: &variant.attrs,
+                    kind: "variant",
                 }
+            })
+            .collect::<Vec<_>>(),
+        Data::Struct(DataStruct { ref fields, .. }) => vec![Variant {
+            ident: name,
+            path: quote!(#name),
+            fields,
+            attrs: &ast.attrs,
+            kind: "struct",
+        }],
+        Data::Union(_) => {
+            let mut errors = Errors::default();
+            errors.push(
+                name.span(),
+                format!(
+                    "#[derive(EnumResponse)] can only be applied to enums and structs. {} is a union.",
+                    name
+                ),
+            );
+            return errors.into_tokens().into();
+        }
+    };
 
-                let fields = fields.iter().enumerate().map(|(i, _)| {
-                    if i == ix {
-                        quote! { status }
-                    } else {
-                        quote! { _ }
-                    }
-                });
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let mut errors = Errors::default();
+    let enum_attrs = match ast.data {
+        Data::Enum(_) => parse_attrs(&ast.attrs, Target::Enum, &mut errors),
+        _ => ResponseAttrs::default(),
+    };
+    if let (Some(span), Some(_)) = (enum_attrs.deny_fallback, &enum_attrs.default_status) {
+        errors.push(
+            span,
+            "response attribute `deny_fallback` cannot be combined with `default_status`",
+        );
+    }
+    let mut status_patterns = Vec::new();
+    let mut reason_patterns = Vec::new();
+    let mut problem_type_patterns = Vec::new();
+    let mut content_type_patterns = Vec::new();
+    let mut code_patterns = Vec::new();
+    let mut codes: Vec<(String, Span)> = Vec::new();
+    let mut descriptors = Vec::new();
+    let mut integrations = enum_attrs.integrations;
+    let mut title_patterns = Vec::new();
+    let mut detail_patterns = Vec::new();
+    let mut body_patterns = Vec::new();
+    let mut delegate_header_patterns = Vec::new();
+    let mut header_patterns = Vec::new();
+    let mut warnings = Vec::new();
+
+    for variant in &variants {
+        let target = if variant.kind == "struct" {
+            Target::Struct
+        } else {
+            Target::Variant
+        };
+        let attrs = parse_attrs(variant.attrs, target, &mut errors);
+        if target == Target::Struct {
+            integrations = attrs.integrations;
+        }
+        let transparent = attrs
+            .transparent
+            .as_ref()
+            .and_then(|&(span, ref source)| match *source {
+                Some(ref source) => Some(source.clone()),
+                None => single_field(variant, span, &mut errors),
+            })
+            .and_then(|source| field_pattern(variant, &source, "transparent", &mut errors));
 
+        match attrs.status {
+            Some(ref status @ ValueSource::Number(_))
+            | Some(ref status @ ValueSource::Constant(_)) => {
+                let pattern = variant_pattern(variant);
+                let status = status_expr(status);
                 status_patterns.push(quote! {
-                                    #enum_name::#variant_name(#(#fields),*) => status,
-                                });
-            }
-            Some(ValueSource::StructField(field_name)) => {
-                let fields = match variant.fields {
-                    Fields::Named(syn::FieldsNamed { ref named, .. }) => named,
-                    _ => panic!("status field only works for struct variants"),
-                };
-
-                let field = fields.iter().find(|f| {
-                    if let Some(ref name) = f.ident {
-                        name == &field_name
-                    } else {
-                        false
-                    }
+                    #pattern => #status,
                 });
-                if let Some(field) = field {
-                    let field_name = &field.ident;
+            }
+            Some(ref source) => {
+                if let Some(pattern) = field_pattern(variant, source, "status_field", &mut errors) {
                     status_patterns.push(quote! {
-                        #enum_name::#variant_name { #field_name, .. } => #field_name,
+                        #pattern => ::enum_response::ToStatusCode::to_status_code(value)
+                            .unwrap_or(::enum_response::StatusCode::INTERNAL_SERVER_ERROR),
                     });
-                } else {
-                    panic!(
-                        "#[response(status_field = \"{}\")] struct field does not exist",
-                        field_name
+                }
+            }
+            None => {
+                if let Some(ref pattern) = transparent {
+                    status_patterns.push(delegate_pattern(pattern, quote!(status)));
+                } else if enum_attrs.deny_fallback.is_some() && enum_attrs.default_status.is_none()
+                {
+                    errors.push(
+                        variant.ident.span(),
+                        format!(
+                            "variant `{}` has no #[response(status = ...)], but the enum \
+                             denies the fallback status",
+                            variant.ident
+                        ),
                     );
                 }
             }
-            None => {}
         }
 
-        match reason {
-            Some(ValueSource::String(reason)) => {
-                let pattern = variant_pattern(enum_name, variant_name, &variant.fields);
-                reason_patterns.push(quote! {
-                                    #pattern => Some(#reason),
-                                });
+        let code = match attrs.code {
+            Some(ValueSource::String(ref code)) => Some((code.value(), code.span())),
+            _ => match (&transparent, &enum_attrs.rename_all) {
+                (None, Some(rule)) => Some((
+                    case::rename(&variant.ident.to_string(), rule),
+                    variant.ident.span(),
+                )),
+                _ => None,
+            },
+        };
+        let descriptor_code = match code {
+            Some((ref code, _)) => quote!(Some(#code)),
+            None => quote!(None),
+        };
+        if let Some((code, span)) = code {
+            let mut previous = codes.iter().filter(|&(other, _)| *other == code);
+            if let Some(&(_, first)) = previous.next() {
+                // report the first occurrence only once, no matter how often it is repeated
+                if previous.next().is_none() {
+                    errors.push(
+                        first,
+                        format!("response code `{}` is used by multiple variants", code),
+                    );
+                }
+                errors.push(span, format!("duplicate response code `{}`", code));
             }
-            Some(ValueSource::TupleField(ix)) => {
-                let fields = match variant.fields {
-                    Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) => unnamed,
-                    _ => panic!("reason index only works for tuple variants"),
-                };
+            let pattern = variant_pattern(variant);
+            code_patterns.push(quote! {
+                #pattern => Some(#code),
+            });
+            codes.push((code, span));
+        } else if let Some(ref pattern) = transparent {
+            code_patterns.push(delegate_pattern(pattern, quote!(code)));
+        }
 
-                if fields.iter().nth(ix).is_none() {
-                    panic!(
-                        "[error(reason_field = {})]: No tuple field at {} found for {}",
-                        ix, ix, variant_name
-                    );
+        // the catalog only contains what is known without constructing the variant
+        let descriptor_status = match attrs.status {
+            Some(ref status @ ValueSource::Number(_))
+            | Some(ref status @ ValueSource::Constant(_)) => Some(status),
+            Some(_) => None,
+            None if transparent.is_some() => None,
+            None => enum_attrs.default_status.as_ref(),
+        };
+        let descriptor_status = match descriptor_status {
+            Some(&ValueSource::Number(status)) => quote! {
+                match ::enum_response::StatusCode::from_u16(#status) {
+                    Ok(status) => Some(status),
+                    Err(_) => None,
                 }
+            },
+            Some(ValueSource::Constant(status)) => {
+                quote!(Some(::enum_response::StatusCode::#status))
+            }
+            Some(_) => unreachable!(),
+            None if attrs.status.is_some() || transparent.is_some() => quote!(None),
+            None => quote!(Some(::enum_response::StatusCode::INTERNAL_SERVER_ERROR)),
+        };
+        let descriptor_reason = match attrs.reason {
+            Some(ValueSource::String(ref reason)) => literal_str(reason),
+            Some(_) => None,
+            None if transparent.is_some() => None,
+            None => match enum_attrs.default_reason {
+                Some(ValueSource::String(ref reason)) => literal_str(reason),
+                _ => None,
+            },
+        };
+        let descriptor_reason = match descriptor_reason {
+            Some(reason) => quote!(Some(#reason)),
+            None => quote!(None),
+        };
+        let variant_name = variant.ident.to_string();
+        descriptors.push(quote! {
+            ::enum_response::Descriptor {
+                variant: #variant_name,
+                status: #descriptor_status,
+                reason: #descriptor_reason,
+                code: #descriptor_code,
+            }
+        });
 
-                let fields = fields.iter().enumerate().map(|(i, _)| {
-                    if i == ix {
-                        quote! { ref reason }
-                    } else {
-                        quote! { _ }
-                    }
+        match attrs.content_type {
+            Some(ValueSource::String(ref content_type)) => {
+                let pattern = variant_pattern(variant);
+                content_type_patterns.push(quote! {
+                    #pattern => Some(#content_type),
                 });
+            }
+            _ => {
+                if let Some(ref pattern) = transparent {
+                    content_type_patterns.push(delegate_pattern(pattern, quote!(content_type)));
+                }
+            }
+        }
 
-                reason_patterns.push(quote! {
-                    #enum_name::#variant_name(#(#fields),*) => Some(reason),
+        match attrs.problem_type {
+            Some(ValueSource::String(ref problem_type)) => {
+                let pattern = variant_pattern(variant);
+                problem_type_patterns.push(quote! {
+                    #pattern => Some(#problem_type),
                 });
             }
-            Some(ValueSource::StructField(field_name)) => {
-                let fields = match variant.fields {
-                    Fields::Named(syn::FieldsNamed { ref named, .. }) => named,
-                    _ => panic!("reason field only works for struct variants"),
-                };
-
-                let field = fields.iter().find(|f| {
-                    if let Some(ref name) = f.ident {
-                        name == &field_name
-                    } else {
-                        false
-                    }
-                });
-                if let Some(field) = field {
-                    let field_name = &field.ident;
-                    reason_patterns.push(quote! {
-                        #enum_name::#variant_name { #field_name, .. } => Some(#field_name),
-                    });
-                } else {
-                    panic!(
-                        "#[response(reason_field = \"{}\")] struct field does not exist",
-                        field_name
-                    );
+            _ => {
+                if let Some(ref pattern) = transparent {
+                    problem_type_patterns.push(delegate_pattern(pattern, quote!(problem_type)));
+                }
+            }
+        }
+
+        if let Some(ref pattern) = transparent {
+            delegate_header_patterns.push(delegate_pattern(pattern, quote!(headers)));
+        }
+        let status = attrs.status.as_ref().and_then(literal_status);
+        let has_challenge = !attrs.challenges.is_empty()
+            || attrs
+                .headers
+                .iter()
+                .any(|header| header.name.value() == "www-authenticate");
+        if status == Some(401) && !has_challenge && !attrs.allow_missing_challenge {
+            warnings.push(missing_challenge_warning(variant));
+        }
+        let has_location = attrs.location.is_some()
+            || attrs
+                .headers
+                .iter()
+                .any(|header| header.name.value() == "location");
+        if let Some(status) = status {
+            // 300 may and 304 must not have a location
+            if (301..400).contains(&status) && status != 304 && !has_location {
+                errors.push(
+                    variant.ident.span(),
+                    format!(
+                        "{} `{}` redirects with status {}, but has no \
+                         #[response(location_field = ...)]",
+                        variant.kind, variant.ident, status
+                    ),
+                );
+            }
+        }
+        if !attrs.headers.is_empty() || attrs.retry_after.is_some() || has_challenge || has_location
+        {
+            if let Some(pattern) = headers_pattern(variant, &attrs, &mut errors) {
+                header_patterns.push(pattern);
+            }
+        }
+
+        // a reason of the variant itself is also its title, instead of the inner value's title
+        let delegate_title = attrs.reason.is_none();
+        let str_attrs = vec![
+            (attrs.reason, &mut reason_patterns, "reason", quote!(reason), true),
+            (attrs.title, &mut title_patterns, "title", quote!(title), delegate_title),
+            (attrs.detail, &mut detail_patterns, "detail", quote!(detail), true),
+            (attrs.body, &mut body_patterns, "body", quote!(body), true),
+        ];
+        for (source, patterns, key, method, delegate) in str_attrs {
+            if let Some(source) = source {
+                if let Some(pattern) = str_pattern(variant, &source, key, &mut errors) {
+                    patterns.push(pattern);
                 }
+            } else if let (Some(ref pattern), true) = (&transparent, delegate) {
+                patterns.push(delegate_pattern(pattern, method));
             }
-            _ => {}
         }
     }
 
+    if !errors.is_empty() {
+        return errors.into_tokens().into();
+    }
+
     if status_patterns.len() < variants.len() {
+        let status = match enum_attrs.default_status {
+            Some(ref status) => status_expr(status),
+            None => quote!(::enum_response::StatusCode::INTERNAL_SERVER_ERROR),
+        };
         status_patterns.push(quote! {
-            _ => ::enum_response::StatusCode::INTERNAL_SERVER_ERROR,
+            _ => #status,
         });
     }
 
     if reason_patterns.len() < variants.len() {
-        reason_patterns.push(quote! {
-            _ => self.status().canonical_reason(),
+        reason_patterns.push(match enum_attrs.default_reason {
+            Some(ValueSource::String(ref reason)) => {
+                let reason = literal_str(reason);
+                quote! {
+                    _ => Some(::std::borrow::Cow::Borrowed(#reason)),
+                }
+            }
+            _ => quote! {
+                _ => self
+                    .status()
+                    .canonical_reason()
+                    .map(::std::borrow::Cow::Borrowed),
+            },
         });
     }
 
+    if code_patterns.len() < variants.len() {
+        code_patterns.push(quote! {
+            _ => None,
+        });
+    }
+
+    if content_type_patterns.len() < variants.len() {
+        content_type_patterns.push(match enum_attrs.content_type {
+            Some(ValueSource::String(ref content_type)) => quote! {
+                _ => Some(#content_type),
+            },
+            _ => quote! {
+                _ => None,
+            },
+        });
+    }
+
+    if problem_type_patterns.len() < variants.len() {
+        problem_type_patterns.push(quote! {
+            _ => None,
+        });
+    }
+
+    if title_patterns.len() < variants.len() {
+        title_patterns.push(quote! {
+            _ => self.reason(),
+        });
+    }
+
+    if detail_patterns.len() < variants.len() {
+        detail_patterns.push(quote! {
+            _ => None,
+        });
+    }
+
+    if body_patterns.len() < variants.len() {
+        body_patterns.push(quote! {
+            _ => None,
+        });
+    }
+
+    if delegate_header_patterns.len() < variants.len() {
+        delegate_header_patterns.push(quote! {
+            _ => ::enum_response::HeaderMap::new(),
+        });
+    }
+
+    let headers_body = if header_patterns.is_empty() {
+        quote! {
+            match *self {
+                #(#delegate_header_patterns)*
+            }
+        }
+    } else {
+        if header_patterns.len() < variants.len() {
+            header_patterns.push(quote! {
+                _ => {}
+            });
+        }
+        quote! {
+            let mut __headers = match *self {
+                #(#delegate_header_patterns)*
+            };
+            match *self {
+                #(#header_patterns)*
+            }
+            __headers
+        }
+    };
+
     let mut status_tokens = Tokens::new();
     status_tokens.append_all(status_patterns);
 
     let mut reason_tokens = Tokens::new();
     reason_tokens.append_all(reason_patterns);
 
+    let mut code_tokens = Tokens::new();
+    code_tokens.append_all(code_patterns);
+
+    let mut content_type_tokens = Tokens::new();
+    content_type_tokens.append_all(content_type_patterns);
+
+    let mut problem_type_tokens = Tokens::new();
+    problem_type_tokens.append_all(problem_type_patterns);
+
+    let mut title_tokens = Tokens::new();
+    title_tokens.append_all(title_patterns);
+
+    let mut detail_tokens = Tokens::new();
+    detail_tokens.append_all(detail_patterns);
+
+    let mut body_tokens = Tokens::new();
+    body_tokens.append_all(body_patterns);
+
+    let integrations = integration_impls(ast, integrations);
     let tokens = quote! {
-        impl #impl_generics ::enum_response::EnumResponse for #enum_name #ty_generics
+        impl #impl_generics ::enum_response::EnumResponse for #name #ty_generics
             #where_clause
         {
             fn status(&self) -> ::enum_response::StatusCode {
@@ -325,26 +565,968 @@ fn derive(ast: &syn::DeriveInput) -> TokenStream {
                 }
             }
 
-            fn reason(&self) -> Option<&str> {
+            fn reason(&self) -> Option<::std::borrow::Cow<'_, str>> {
                 match *self {
                     #reason_tokens
                 }
             }
+
+            fn code(&self) -> Option<&str> {
+                match *self {
+                    #code_tokens
+                }
+            }
+
+            fn content_type(&self) -> Option<&str> {
+                match *self {
+                    #content_type_tokens
+                }
+            }
+
+            fn problem_type(&self) -> Option<&str> {
+                match *self {
+                    #problem_type_tokens
+                }
+            }
+
+            fn title(&self) -> Option<::std::borrow::Cow<'_, str>> {
+                match *self {
+                    #title_tokens
+                }
+            }
+
+            fn detail(&self) -> Option<::std::borrow::Cow<'_, str>> {
+                match *self {
+                    #detail_tokens
+                }
+            }
+
+            fn body(&self) -> Option<::std::borrow::Cow<'_, str>> {
+                match *self {
+                    #body_tokens
+                }
+            }
+
+            fn headers(&self) -> ::enum_response::HeaderMap {
+                #headers_body
+            }
+        }
+
+        impl #impl_generics ::enum_response::Catalog for #name #ty_generics
+            #where_clause
+        {
+            const CATALOG: &'static [::enum_response::Descriptor] = &[
+                #(#descriptors),*
+            ];
         }
+
+        #integrations
+
+        #(#warnings)*
     };
     tokens.into()
 }
 
-fn variant_pattern(enum_name: &Ident, variant_name: &Ident, variant_data: &Fields) -> Tokens {
-    match variant_data {
+/// Implements the traits of the web frameworks requested with attributes like
+/// `#[response(axum)]`. The features are only known to `enum-response` as built for the user's
+/// crate, so the impls are generated by macros it exports, which fail to compile if the feature is
+/// disabled.
+fn integration_impls(ast: &syn::DeriveInput, integrations: Integrations) -> Tokens {
+    let name = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
+    let mut tokens = Tokens::new();
+
+    if integrations.actix {
+        tokens.append_all(quote! {
+            ::enum_response::__impl_actix!([#impl_generics] [#name #ty_generics] [#where_clause]);
+        });
+    }
+
+    if integrations.axum {
+        tokens.append_all(quote! {
+            ::enum_response::__impl_axum!([#impl_generics] [#name #ty_generics] [#where_clause]);
+        });
+    }
+
+    if integrations.rocket {
+        // the impl generics of `Responder` without the defaults of the type's generics
+        let mut responder_generics = ast.generics.clone();
+        responder_generics.params.insert(0, parse_quote!('__o: '__r));
+        responder_generics.params.insert(0, parse_quote!('__r));
+        let (responder_impl_generics, _, _) = responder_generics.split_for_impl();
+        tokens.append_all(quote! {
+            ::enum_response::__impl_rocket!(
+                ['__r, '__o]
+                [#responder_impl_generics] [#name #ty_generics] [#where_clause]
+            );
+        });
+    }
+
+    if integrations.warp {
+        tokens.append_all(quote! {
+            ::enum_response::__impl_warp!([#impl_generics] [#name #ty_generics] [#where_clause]);
+        });
+    }
+
+    tokens
+}
+
+fn parse_attrs(attrs: &[Attribute], target: Target, errors: &mut Errors) -> ResponseAttrs {
+    let mut result = ResponseAttrs::default();
+
+    for attr in attrs {
+        if attr.path.segments.len() != 1 || attr.path.segments[0].ident != "response" {
+            continue;
+        }
+
+        let nested = match attr.interpret_meta() {
+            Some(Meta::List(MetaList { nested, .. })) => nested,
+            _ => {
+                errors.push(
+                    attr.path.span(),
+                    "expected response attribute of the form `#[response(key = value, ...)]`",
+                );
+                continue;
+            }
+        };
+
+        for item in &nested {
+            let (name, val, list) = match *item {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                    ref ident, ref lit, ..
+                })) => (ident, Some(lit), None),
+                NestedMeta::Meta(Meta::Word(ref name)) => (name, None, None),
+                NestedMeta::Meta(Meta::List(ref list)) => (&list.ident, None, Some(list)),
+                NestedMeta::Literal(ref lit) => {
+                    errors.push(lit.span(), "unexpected literal in response attribute");
+                    continue;
+                }
+            };
+
+            let key = name.to_string();
+            let placement = if SHARED_KEYS.contains(&key.as_str()) {
+                None
+            } else if ENUM_KEYS.contains(&key.as_str()) {
+                Some((target == Target::Enum, "enums"))
+            } else if TYPE_KEYS.contains(&key.as_str()) {
+                Some((target != Target::Variant, "enums and structs"))
+            } else {
+                Some((target != Target::Enum, "variants and structs"))
+            };
+            if let Some((false, allowed)) = placement {
+                errors.push(
+                    name.span(),
+                    format!(
+                        "response attribute `{}` can only be used on {}",
+                        key, allowed
+                    ),
+                );
+                continue;
+            }
+
+            if let Some(list) = list {
+                match key.as_str() {
+                    "header" => result.headers.extend(parse_header(list, errors)),
+                    "challenge" => result.challenges.extend(parse_challenge(list, errors)),
+                    _ => errors.push(
+                        name.span(),
+                        format!("unknown response attribute `{}`", name),
+                    ),
+                }
+                continue;
+            }
+
+            match (key.as_str(), val) {
+                ("status", Some(val)) => result.status = parse_status(val, errors),
+                ("status_field", Some(val)) => {
+                    result.status = parse_field(val, "status_field", errors)
+                }
+                ("reason", Some(val)) => result.reason = parse_str(val, "reason", errors),
+                ("reason_field", Some(val)) => {
+                    result.reason = parse_field(val, "reason_field", errors)
+                }
+                ("code", Some(val)) => result.code = parse_code(val, errors),
+                ("rename_all", Some(val)) => result.rename_all = parse_rename_all(val, errors),
+                ("content_type", Some(val)) => {
+                    result.content_type = parse_content_type(val, errors)
+                }
+                ("type", Some(val)) => result.problem_type = parse_str(val, "type", errors),
+                ("title", Some(val)) => result.title = parse_str(val, "title", errors),
+                ("detail", Some(val)) => result.detail = parse_str(val, "detail", errors),
+                ("detail_field", Some(val)) => {
+                    result.detail = parse_field(val, "detail_field", errors)
+                }
+                ("body", Some(val)) => result.body = parse_str(val, "body", errors),
+                ("body_field", Some(val)) => result.body = parse_field(val, "body_field", errors),
+                ("default_status", Some(val)) => result.default_status = parse_status(val, errors),
+                ("default_reason", Some(val)) => {
+                    result.default_reason = parse_default_reason(val, errors)
+                }
+                ("transparent", None) => result.transparent = Some((name.span(), None)),
+                ("transparent", Some(val)) => {
+                    result.transparent =
+                        Some((name.span(), parse_field(val, "transparent", errors)))
+                }
+                ("retry_after_field", Some(val)) => {
+                    result.retry_after = parse_field(val, "retry_after_field", errors)
+                }
+                ("location_field", Some(val)) => {
+                    result.location = parse_field(val, "location_field", errors)
+                }
+                ("allow_missing_challenge", None) => result.allow_missing_challenge = true,
+                ("actix", None) => result.integrations.actix = true,
+                ("axum", None) => result.integrations.axum = true,
+                ("rocket", None) => result.integrations.rocket = true,
+                ("warp", None) => result.integrations.warp = true,
+                ("deny_fallback", None) => result.deny_fallback = Some(name.span()),
+                _ => errors.push(
+                    name.span(),
+                    format!("unknown response attribute `{}`", name),
+                ),
+            }
+        }
+    }
+
+    result
+}
+
+fn parse_header(list: &MetaList, errors: &mut Errors) -> Option<Header> {
+    let mut name = None;
+    let mut value = None;
+    for item in &list.nested {
+        match *item {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ref ident, ref lit, ..
+            })) => match (ident.to_string().as_str(), lit) {
+                ("name", Lit::Str(lit)) => {
+                    let header = lit.value().to_lowercase();
+                    if ::enum_response::HeaderName::from_bytes(header.as_bytes()).is_err() {
+                        errors.push(lit.span(), format!("invalid header name `{}`", header));
+                    }
+                    name = Some(LitStr::new(&header, lit.span()));
+                }
+                ("value", Lit::Str(lit)) => {
+                    if !is_visible_ascii(&lit.value()) {
+                        errors.push(
+                            lit.span(),
+                            "invalid header value, only visible ASCII characters are allowed",
+                        );
+                    }
+                    value = Some(ValueSource::String(lit.clone()));
+                }
+                ("value_field", lit) => value = parse_field(lit, "value_field", errors),
+                _ => errors.push(
+                    ident.span(),
+                    format!("unexpected response header attribute `{} = ...`", ident),
+                ),
+            },
+            _ => errors.push(
+                item.span(),
+                "expected response header attribute `name`, `value` or `value_field`",
+            ),
+        }
+    }
+
+    match (name, value) {
+        (Some(name), Some(value)) => Some(Header { name, value }),
+        _ => {
+            errors.push(
+                list.ident.span(),
+                "expected response header of the form \
+                 `header(name = \"...\", value = \"...\")` or \
+                 `header(name = \"...\", value_field = ...)`",
+            );
+            None
+        }
+    }
+}
+
+fn parse_challenge(list: &MetaList, errors: &mut Errors) -> Option<Challenge> {
+    let mut scheme = None;
+    let mut params = Vec::new();
+    for item in &list.nested {
+        let (ident, lit) = match *item {
+            NestedMeta::Meta(Meta::NameValue(MetaNameValue {
+                ref ident, ref lit, ..
+            })) => (ident, lit),
+            _ => {
+                errors.push(
+                    item.span(),
+                    "expected response challenge attribute of the form `key = value`",
+                );
+                continue;
+            }
+        };
+
+        let key = ident.to_string();
+        if key == "scheme" {
+            match *lit {
+                Lit::Str(ref s) => {
+                    if !is_token(&s.value()) {
+                        errors.push(s.span(), "response challenge scheme must be a token");
+                    }
+                    scheme = Some(s.value());
+                }
+                _ => errors.push(
+                    lit.span(),
+                    "response challenge scheme must be of type string",
+                ),
+            }
+        } else if key.ends_with("_field") {
+            let name = key.trim_end_matches("_field").to_string();
+            params.extend(parse_field(lit, &key, errors).map(|source| (name, source)));
+        } else if let Some(value) = parse_str(lit, "challenge parameter", errors) {
+            if let ValueSource::String(ref s) = value {
+                if ::enum_response::HeaderValue::from_str(&s.value()).is_err() {
+                    errors.push(s.span(), "invalid challenge parameter value");
+                }
+            }
+            params.push((key, value));
+        }
+    }
+
+    match scheme {
+        Some(scheme) => Some(Challenge { scheme, params }),
+        None => {
+            errors.push(
+                list.ident.span(),
+                "response challenge requires a scheme, e.g. `challenge(scheme = \"Bearer\")`",
+            );
+            None
+        }
+    }
+}
+
+/// Whether `s` only consists of visible ASCII characters and tabs, which is required by
+/// `HeaderValue::from_static` used for literal header values.
+fn is_visible_ascii(s: &str) -> bool {
+    s.bytes()
+        .all(|b| (b' '..b'\x7f').contains(&b) || b == b'\t')
+}
+
+/// Whether `s` is a token as defined in RFC 7230, e.g. an authentication scheme.
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c))
+}
+
+fn parse_code(lit: &Lit, errors: &mut Errors) -> Option<ValueSource> {
+    let code = parse_str(lit, "code", errors)?;
+    if let ValueSource::String(ref s) = code {
+        if s.value().is_empty() {
+            errors.push(s.span(), "response code must not be empty");
+            return None;
+        }
+    }
+    Some(code)
+}
+
+fn parse_rename_all(lit: &Lit, errors: &mut Errors) -> Option<String> {
+    match *lit {
+        Lit::Str(ref s) if case::RULES.contains(&s.value().as_str()) => Some(s.value()),
+        _ => {
+            errors.push(
+                lit.span(),
+                format!(
+                    "response rename_all attribute value must be one of {}",
+                    case::RULES
+                        .iter()
+                        .map(|rule| format!("`{}`", rule))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            );
+            None
+        }
+    }
+}
+
+fn parse_content_type(lit: &Lit, errors: &mut Errors) -> Option<ValueSource> {
+    let content_type = parse_str(lit, "content_type", errors)?;
+    if let ValueSource::String(ref s) = content_type {
+        let value = s.value();
+        let media_type = value.split(';').next().unwrap_or("");
+        let mut parts = media_type.trim().split('/');
+        let valid = match (parts.next(), parts.next(), parts.next()) {
+            (Some(ty), Some(subtype), None) => is_token(ty) && is_token(subtype),
+            _ => false,
+        };
+        if !valid || ::enum_response::HeaderValue::from_str(&value).is_err() {
+            errors.push(s.span(), format!("invalid content type `{}`", value));
+            return None;
+        }
+        let formatters = ::enum_response::formatter::FORMATTERS;
+        if ::enum_response::formatter::find(&value, formatters).is_none() {
+            let media_types = formatters
+                .iter()
+                .flat_map(|formatter| formatter.media_types())
+                .map(|media_type| format!("`{}`", media_type))
+                .collect::<Vec<_>>();
+            errors.push(
+                s.span(),
+                format!(
+                    "no built-in formatter renders content type `{}`, expected one of {}",
+                    value,
+                    media_types.join(", ")
+                ),
+            );
+            return None;
+        }
+    }
+    Some(content_type)
+}
+
+/// Parses `default_reason`, which is used for variants with different fields and can therefore
+/// not refer to any, but escapes braces the same way as format strings.
+fn parse_default_reason(lit: &Lit, errors: &mut Errors) -> Option<ValueSource> {
+    let reason = parse_str(lit, "default_reason", errors)?;
+    if let ValueSource::String(ref s) = reason {
+        match format::parse(&s.value()) {
+            Ok(ref format) if format.args.is_empty() => {}
+            Ok(_) => {
+                errors.push(
+                    s.span(),
+                    "#[response(default_reason = ...)] cannot refer to fields",
+                );
+                return None;
+            }
+            Err(msg) => {
+                errors.push(s.span(), msg);
+                return None;
+            }
+        }
+    }
+    Some(reason)
+}
+
+fn parse_status(lit: &Lit, errors: &mut Errors) -> Option<ValueSource> {
+    match *lit {
+        Lit::Int(ref status) => check_status(status.value(), lit, errors),
+        Lit::Str(ref name) => {
+            let name = name.value();
+            if let Ok(status) = u64::from_str(name.as_str()) {
+                check_status(status, lit, errors)
+            } else if let Ok(mut name) = syn::parse_str::<Ident>(&name) {
+                name.set_span(lit.span());
+                Some(ValueSource::Constant(name))
+            } else {
+                errors.push(lit.span(), format!("invalid status `{}`", name));
+                None
+            }
+        }
+        _ => {
+            errors.push(
+                lit.span(),
+                "response status attribute value must be of type int or string",
+            );
+            None
+        }
+    }
+}
+
+fn check_status(status: u64, lit: &Lit, errors: &mut Errors) -> Option<ValueSource> {
+    // make sure it is a valid status code
+    if status > u64::from(u16::MAX) || ::enum_response::StatusCode::from_u16(status as u16).is_err()
+    {
+        errors.push(lit.span(), format!("invalid status code `{}`", status));
+        return None;
+    }
+    Some(ValueSource::Number(status as u16))
+}
+
+fn parse_str(lit: &Lit, key: &str, errors: &mut Errors) -> Option<ValueSource> {
+    match *lit {
+        Lit::Str(ref s) => Some(ValueSource::String(s.clone())),
+        _ => {
+            errors.push(
+                lit.span(),
+                format!("response {} attribute value must be of type string", key),
+            );
+            None
+        }
+    }
+}
+
+fn parse_field(lit: &Lit, attr: &str, errors: &mut Errors) -> Option<ValueSource> {
+    match *lit {
+        Lit::Int(ref ix) => Some(ValueSource::TupleField(ix.value() as usize, lit.span())),
+        Lit::Str(ref s) => {
+            let s = s.value();
+            Some(match usize::from_str(&s) {
+                Ok(ix) => ValueSource::TupleField(ix, lit.span()),
+                Err(_) => ValueSource::StructField(s, lit.span()),
+            })
+        }
+        _ => {
+            errors.push(
+                lit.span(),
+                format!(
+                    "response {} attribute value must be of type int or string",
+                    attr
+                ),
+            );
+            None
+        }
+    }
+}
+
+/// The value of a string literal without format arguments, with escaped braces unescaped.
+fn literal_str(lit: &LitStr) -> Option<String> {
+    let value = lit.value();
+    match format::parse(&value) {
+        Ok(ref format) if format.args.is_empty() => {
+            Some(value.replace("{{", "{").replace("}}", "}"))
+        }
+        _ => None,
+    }
+}
+
+/// Builds the match arm returning `Some(Cow<str>)` for `reason`, `title`, `detail` and `body`.
+fn str_pattern(
+    variant: &Variant,
+    source: &ValueSource,
+    key: &str,
+    errors: &mut Errors,
+) -> Option<Tokens> {
+    match *source {
+        ValueSource::String(ref lit) => {
+            let value = lit.value();
+            let format = match format::parse(&value) {
+                Ok(format) => format,
+                Err(msg) => {
+                    errors.push(lit.span(), msg);
+                    return None;
+                }
+            };
+
+            if format.args.is_empty() {
+                let pattern = variant_pattern(variant);
+                let value = value.replace("{{", "{").replace("}}", "}");
+                return Some(quote! {
+                    #pattern => Some(::std::borrow::Cow::Borrowed(#value)),
+                });
+            }
+
+            let pattern = fields_pattern(variant, &format.args, lit.span(), key, errors)?;
+            let fmt = &format.format;
+            let names = format
+                .args
+                .iter()
+                .map(|arg| Ident::new(&arg.binding(), Span::call_site()))
+                .collect::<Vec<_>>();
+            let names = &names;
+            let values = names;
+            Some(quote! {
+                #pattern => Some(::std::borrow::Cow::Owned(format!(#fmt, #(#names = #values),*))),
+            })
+        }
+        _ => field_pattern(variant, source, &format!("{}_field", key), errors).map(|pattern| {
+            quote! {
+                #pattern => {
+                    use ::enum_response::__private::{DisplayReason, StrReason};
+                    Some((&::enum_response::__private::Reason(value)).to_reason())
+                }
+            }
+        }),
+    }
+}
+
+/// Builds the match arm appending the `#[response(header(...))]`, `retry_after_field`,
+/// `location_field` and `challenge(...)` headers of `variant` to `__headers`.
+fn headers_pattern(
+    variant: &Variant,
+    attrs: &ResponseAttrs,
+    errors: &mut Errors,
+) -> Option<Tokens> {
+    let fields = attrs
+        .headers
+        .iter()
+        .map(|header| (&header.value, String::from("value_field")))
+        .chain(
+            attrs
+                .retry_after
+                .iter()
+                .map(|source| (source, String::from("retry_after_field"))),
+        )
+        .chain(
+            attrs
+                .location
+                .iter()
+                .map(|source| (source, String::from("location_field"))),
+        )
+        .chain(attrs.challenges.iter().flat_map(|challenge| {
+            challenge
+                .params
+                .iter()
+                .map(|(name, source)| (source, format!("{}_field", name)))
+        }));
+    let mut args = Vec::new();
+    let mut valid = true;
+    for (source, attr) in fields {
+        let arg = match *source {
+            ValueSource::TupleField(ix, _) => FormatArg::Index(ix),
+            ValueSource::StructField(ref name, _) => FormatArg::Named(name.clone()),
+            _ => continue,
+        };
+        // report missing fields the same way as for all other `_field` attributes
+        if field_pattern(variant, source, &attr, errors).is_none() {
+            valid = false;
+        } else if !args.contains(&arg) {
+            args.push(arg);
+        }
+    }
+    if !valid {
+        return None;
+    }
+
+    let pattern = fields_pattern(variant, &args, Span::call_site(), "header", errors)?;
+    let retry_after = attrs.retry_after.as_ref().map(|source| {
+        let binding = Ident::new(&binding(source), Span::call_site());
+        quote! {
+            if let Some(value) = ::enum_response::RetryAfter::retry_after(#binding) {
+                __headers.insert(::enum_response::HeaderName::from_static("retry-after"), value);
+            }
+        }
+    });
+    let appends = attrs.headers.iter().map(|header| {
+        let name = &header.name;
+        let name = quote!(::enum_response::HeaderName::from_static(#name));
+        match header.value {
+            ValueSource::String(ref value) => quote! {
+                __headers.append(#name, ::enum_response::HeaderValue::from_static(#value));
+            },
+            ref source => header_field_append(&name, &binding(source)),
+        }
+    });
+    let location = attrs.location.as_ref().map(|source| {
+        let name = quote!(::enum_response::HeaderName::from_static("location"));
+        header_field_append(&name, &binding(source))
+    });
+    let challenges = attrs.challenges.iter().map(challenge_append);
+    Some(quote! {
+        #pattern => {
+            #(#appends)*
+            #retry_after
+            #location
+            #(#challenges)*
+        }
+    })
+}
+
+/// Appends a `WWW-Authenticate` header for `challenge`.
+fn challenge_append(challenge: &Challenge) -> Tokens {
+    let scheme = &challenge.scheme;
+    let names = challenge.params.iter().map(|(name, _)| name);
+    let values = challenge.params.iter().map(|(_, source)| match *source {
+        ValueSource::String(ref value) => quote!(#value),
+        ref source => {
+            let binding = Ident::new(&binding(source), Span::call_site());
+            quote!((&::enum_response::__private::Reason(#binding)).to_reason())
+        }
+    });
+    let imports = if challenge.params.iter().any(|(_, source)| is_field(source)) {
+        Some(quote!(
+            use ::enum_response::__private::{DisplayReason, StrReason};
+        ))
+    } else {
+        None
+    };
+    quote! {
+        {
+            #imports
+            let challenge = ::enum_response::Challenge::new(#scheme)
+                #(.param(#names, #values))*;
+            if let Some(value) = challenge.to_header_value() {
+                __headers.append(
+                    ::enum_response::HeaderName::from_static("www-authenticate"),
+                    value,
+                );
+            }
+        }
+    }
+}
+
+fn is_field(source: &ValueSource) -> bool {
+    matches!(
+        *source,
+        ValueSource::TupleField(..) | ValueSource::StructField(..)
+    )
+}
+
+/// Emits a warning for a 401 variant without `WWW-Authenticate` challenge, which is required by
+/// RFC 7235. Stable proc macros cannot emit warnings, so this uses a deprecated constant instead.
+fn missing_challenge_warning(variant: &Variant) -> Tokens {
+    let note = format!(
+        "{} `{}` responds with 401 Unauthorized, but has no WWW-Authenticate challenge; add \
+         #[response(challenge(scheme = \"...\"))], or opt out with \
+         #[response(allow_missing_challenge)]",
+        variant.kind, variant.ident
+    );
+    quote_spanned! {variant.ident.span()=>
+        const _: () = {
+            #[deprecated(note = #note)]
+            const MISSING_CHALLENGE: () = ();
+            MISSING_CHALLENGE
+        };
+    }
+}
+
+/// The name a field selected by `source` is bound to by `fields_pattern`.
+fn binding(source: &ValueSource) -> String {
+    match *source {
+        ValueSource::TupleField(ix, _) => format!("_{}", ix),
+        ValueSource::StructField(ref field, _) => field.clone(),
+        _ => unreachable!(),
+    }
+}
+
+/// Appends the field bound as `binding`, if it is a valid header value.
+fn header_field_append(name: &Tokens, binding: &str) -> Tokens {
+    let binding = Ident::new(binding, Span::call_site());
+    quote! {
+        {
+            use ::enum_response::__private::{DisplayReason, StrReason};
+            let value = (&::enum_response::__private::Reason(#binding)).to_reason();
+            if let Ok(value) = ::enum_response::HeaderValue::from_str(&value) {
+                __headers.append(#name, value);
+            }
+        }
+    }
+}
+
+/// Builds a pattern for `variant` that binds all fields referenced by `args` to the names of
+/// their format arguments.
+fn fields_pattern(
+    variant: &Variant,
+    args: &[FormatArg],
+    span: Span,
+    key: &str,
+    errors: &mut Errors,
+) -> Option<Tokens> {
+    let mut valid = true;
+    for arg in args {
+        let exists = match (variant.fields, arg) {
+            (Fields::Named(fields), FormatArg::Named(name)) => fields
+                .named
+                .iter()
+                .any(|f| f.ident.as_ref().is_some_and(|f| f == name)),
+            (Fields::Unnamed(fields), &FormatArg::Index(ix)) => ix < fields.unnamed.len(),
+            _ => false,
+        };
+        if !exists {
+            errors.push(
+                span,
+                format!(
+                    "#[response({} = ...)] refers to `{{{}}}`, but {} `{}` has no such field",
+                    key,
+                    arg.binding().trim_start_matches('_'),
+                    variant.kind,
+                    variant.ident
+                ),
+            );
+            valid = false;
+        }
+    }
+    if !valid {
+        return None;
+    }
+
+    if args.is_empty() {
+        return Some(variant_pattern(variant));
+    }
+
+    let path = &variant.path;
+    match *variant.fields {
+        Fields::Named(_) => {
+            let bindings = args
+                .iter()
+                .map(|arg| Ident::new(&arg.binding(), Span::call_site()));
+            Some(quote!(#path { #(ref #bindings,)* .. }))
+        }
+        Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) => {
+            let fields = (0..unnamed.len()).map(|i| {
+                if args.contains(&FormatArg::Index(i)) {
+                    let binding = Ident::new(&format!("_{}", i), Span::call_site());
+                    quote! { ref #binding }
+                } else {
+                    quote! { _ }
+                }
+            });
+            Some(quote!(#path(#(#fields),*)))
+        }
+        Fields::Unit => unreachable!(),
+    }
+}
+
+/// Builds a pattern for `variant` that binds the field referenced by `source` as `value`. `attr`
+/// is the name of the attribute `source` originates from, e.g. `reason_field`.
+fn field_pattern(
+    variant: &Variant,
+    source: &ValueSource,
+    attr: &str,
+    errors: &mut Errors,
+) -> Option<Tokens> {
+    let path = &variant.path;
+    let key = attr.trim_end_matches("_field");
+    match *source {
+        ValueSource::TupleField(ix, span) => {
+            let fields = match *variant.fields {
+                Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) => unnamed,
+                _ => {
+                    errors.push(
+                        span,
+                        format!("{} index only works for tuple {}s", key, variant.kind),
+                    );
+                    return None;
+                }
+            };
+
+            if ix >= fields.len() {
+                errors.push(
+                    span,
+                    format!(
+                        "#[response({} = {})]: No tuple field at {} found for {}",
+                        attr, ix, ix, variant.ident
+                    ),
+                );
+                return None;
+            }
+
+            let fields = (0..fields.len()).map(|i| {
+                if i == ix {
+                    quote! { ref value }
+                } else {
+                    quote! { _ }
+                }
+            });
+
+            Some(quote! {
+                #path(#(#fields),*)
+            })
+        }
+        ValueSource::StructField(ref field_name, span) => {
+            let fields = match *variant.fields {
+                Fields::Named(syn::FieldsNamed { ref named, .. }) => named,
+                _ => {
+                    errors.push(
+                        span,
+                        format!(
+                            "{} field only works for {}s with named fields",
+                            key, variant.kind
+                        ),
+                    );
+                    return None;
+                }
+            };
+
+            let field = fields.iter().find(|f| {
+                if let Some(ref name) = f.ident {
+                    name == field_name
+                } else {
+                    false
+                }
+            });
+            if let Some(field) = field {
+                let field_name = &field.ident;
+                Some(quote! {
+                    #path { #field_name: ref value, .. }
+                })
+            } else {
+                errors.push(
+                    span,
+                    format!(
+                        "#[response({} = \"{}\")] struct field does not exist",
+                        attr, field_name
+                    ),
+                );
+                None
+            }
+        }
+        ValueSource::Number(_) | ValueSource::String(_) | ValueSource::Constant(_) => {
+            unreachable!()
+        }
+    }
+}
+
+/// Builds a match arm that delegates `method` to the `EnumResponse` impl of the field bound by
+/// `pattern`.
+fn delegate_pattern(pattern: &Tokens, method: Tokens) -> Tokens {
+    quote! {
+        #pattern => {
+            use ::enum_response::EnumResponse;
+            value.#method()
+        }
+    }
+}
+
+/// Selects the only field of `variant`, which is required for `#[response(transparent)]`.
+fn single_field(variant: &Variant, span: Span, errors: &mut Errors) -> Option<ValueSource> {
+    match *variant.fields {
+        Fields::Unnamed(syn::FieldsUnnamed { ref unnamed, .. }) if unnamed.len() == 1 => {
+            Some(ValueSource::TupleField(0, span))
+        }
+        Fields::Named(syn::FieldsNamed { ref named, .. }) if named.len() == 1 => {
+            let name = named[0].ident.as_ref().unwrap();
+            Some(ValueSource::StructField(name.to_string(), span))
+        }
+        _ => {
+            errors.push(
+                span,
+                format!(
+                    "#[response(transparent)] requires {} `{}` to have exactly one field, \
+                     use #[response(transparent = ...)] to select one",
+                    variant.kind, variant.ident
+                ),
+            );
+            None
+        }
+    }
+}
+
+/// The numeric value of a status given as literal, as far as it is needed to validate the other
+/// attributes of a variant.
+fn literal_status(status: &ValueSource) -> Option<u16> {
+    match *status {
+        ValueSource::Number(status) => Some(status),
+        ValueSource::Constant(ref name) => match name.to_string().as_str() {
+            "MULTIPLE_CHOICES" => Some(300),
+            "MOVED_PERMANENTLY" => Some(301),
+            "FOUND" => Some(302),
+            "SEE_OTHER" => Some(303),
+            "NOT_MODIFIED" => Some(304),
+            "USE_PROXY" => Some(305),
+            "TEMPORARY_REDIRECT" => Some(307),
+            "PERMANENT_REDIRECT" => Some(308),
+            "UNAUTHORIZED" => Some(401),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Builds the expression for a status given as a number or as a `StatusCode` constant name.
+fn status_expr(status: &ValueSource) -> Tokens {
+    match *status {
+        ValueSource::Number(status) => {
+            quote!(::enum_response::StatusCode::from_u16(#status).unwrap())
+        }
+        ValueSource::Constant(ref status) => quote!(::enum_response::StatusCode::#status),
+        _ => unreachable!(),
+    }
+}
+
+fn variant_pattern(variant: &Variant) -> Tokens {
+    let path = &variant.path;
+    match *variant.fields {
         Fields::Unit => {
-            quote!{ #enum_name::#variant_name }
+            quote! { #path }
         }
         Fields::Unnamed(_) => {
-            quote! { #enum_name::#variant_name(..) }
+            quote! { #path(..) }
         }
         Fields::Named(_) => {
-            quote! { #enum_name::#variant_name { .. } }
+            quote! { #path { .. } }
         }
     }
 }
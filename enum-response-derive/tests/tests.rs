@@ -1,26 +1,18 @@
-#![feature(attr_literals)]
+#![allow(dead_code)]
 
-extern crate compiletest_rs as compiletest;
 extern crate enum_response;
 #[macro_use]
 extern crate enum_response_derive;
+extern crate trybuild;
 
-use std::path::PathBuf;
-use enum_response::{EnumResponse, StatusCode};
+use std::borrow::Cow;
 
-fn run_mode(mode: &'static str) {
-    let mut config = compiletest::Config::default();
-
-    config.mode = mode.parse().expect("Invalid mode");
-    config.src_base = PathBuf::from(format!("tests/{}", mode));
-    config.link_deps(); // Populate config.target_rustcflags with dependencies on the path
-
-    compiletest::run_tests(&config);
-}
+use enum_response::{EnumResponse, Problem, Response, StatusCode, ToStatusCode};
 
 #[test]
 fn compile_test() {
-    run_mode("ui");
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
 }
 
 #[test]
@@ -32,15 +24,15 @@ fn default_internal_server_error() {
         Struct { s: &'a str },
     }
     assert_eq!(Error::Unit.status(), StatusCode::INTERNAL_SERVER_ERROR);
-    assert_eq!(Error::Unit.reason(), Some("Internal Server Error"));
+    assert_eq!(Error::Unit.reason().as_deref(), Some("Internal Server Error"));
     assert_eq!(Error::Tuple("").status(), StatusCode::INTERNAL_SERVER_ERROR);
-    assert_eq!(Error::Tuple("").reason(), Some("Internal Server Error"));
+    assert_eq!(Error::Tuple("").reason().as_deref(), Some("Internal Server Error"));
     assert_eq!(
         Error::Struct { s: "" }.status(),
         StatusCode::INTERNAL_SERVER_ERROR
     );
     assert_eq!(
-        Error::Struct { s: "" }.reason(),
+        Error::Struct { s: "" }.reason().as_deref(),
         Some("Internal Server Error")
     );
 }
@@ -51,7 +43,7 @@ fn override_status_code_int() {
     enum Error<'a> {
         #[response(status = 400)]
         Unit,
-        #[response(status = 401)]
+        #[response(status = 401, allow_missing_challenge)]
         Tuple(&'a str),
         #[response(status = 402)]
         Struct { s: &'a str },
@@ -72,7 +64,7 @@ fn override_status_code_string() {
         Unit,
         #[response(status = "400")]
         Tuple(&'a str),
-        #[response(status = "401")]
+        #[response(status = "401", allow_missing_challenge)]
         Struct { s: &'a str },
     }
     assert_eq!(Error::Unit.status(), StatusCode::PAYMENT_REQUIRED);
@@ -84,7 +76,7 @@ fn override_status_code_string() {
 fn override_status_name() {
     #[derive(Debug, EnumResponse)]
     enum Error<'a> {
-        #[response(status = "UNAUTHORIZED")]
+        #[response(status = "UNAUTHORIZED", allow_missing_challenge)]
         Unit,
         #[response(status = "PAYMENT_REQUIRED")]
         Tuple(&'a str),
@@ -107,9 +99,9 @@ fn override_reason() {
         #[response(reason = "C")]
         Struct { s: &'a str },
     }
-    assert_eq!(Error::Unit.reason(), Some("A"));
-    assert_eq!(Error::Tuple("").reason(), Some("B"));
-    assert_eq!(Error::Struct { s: "" }.reason(), Some("C"));
+    assert_eq!(Error::Unit.reason().as_deref(), Some("A"));
+    assert_eq!(Error::Tuple("").reason().as_deref(), Some("B"));
+    assert_eq!(Error::Struct { s: "" }.reason().as_deref(), Some("C"));
 }
 
 #[test]
@@ -120,7 +112,7 @@ fn struct_variant() {
     }
     let err = Error::Struct { s: "" };
     assert_eq!(err.status(), StatusCode::INTERNAL_SERVER_ERROR);
-    assert_eq!(err.reason(), Some("Internal Server Error"));
+    assert_eq!(err.reason().as_deref(), Some("Internal Server Error"));
 }
 
 #[test]
@@ -139,11 +131,11 @@ fn override_reason_from_tuple_field_int() {
         Last(&'a str, &'a str, &'a str),
     }
 
-    assert_eq!(Error::One(String::from("a")).reason(), Some("a"));
-    assert_eq!(Error::Two("a", "b").reason(), Some("b"));
-    assert_eq!(Error::First("a", "b", "c").reason(), Some("a"));
-    assert_eq!(Error::Inbetween("a", "b", "c").reason(), Some("b"));
-    assert_eq!(Error::Last("a", "b", "c").reason(), Some("c"));
+    assert_eq!(Error::One(String::from("a")).reason().as_deref(), Some("a"));
+    assert_eq!(Error::Two("a", "b").reason().as_deref(), Some("b"));
+    assert_eq!(Error::First("a", "b", "c").reason().as_deref(), Some("a"));
+    assert_eq!(Error::Inbetween("a", "b", "c").reason().as_deref(), Some("b"));
+    assert_eq!(Error::Last("a", "b", "c").reason().as_deref(), Some("c"));
 }
 
 #[test]
@@ -162,11 +154,11 @@ fn override_reason_from_tuple_field_string() {
         Last(&'a str, &'a str, &'a str),
     }
 
-    assert_eq!(Error::One(String::from("a")).reason(), Some("a"));
-    assert_eq!(Error::Two("a", "b").reason(), Some("b"));
-    assert_eq!(Error::First("a", "b", "c").reason(), Some("a"));
-    assert_eq!(Error::Inbetween("a", "b", "c").reason(), Some("b"));
-    assert_eq!(Error::Last("a", "b", "c").reason(), Some("c"));
+    assert_eq!(Error::One(String::from("a")).reason().as_deref(), Some("a"));
+    assert_eq!(Error::Two("a", "b").reason().as_deref(), Some("b"));
+    assert_eq!(Error::First("a", "b", "c").reason().as_deref(), Some("a"));
+    assert_eq!(Error::Inbetween("a", "b", "c").reason().as_deref(), Some("b"));
+    assert_eq!(Error::Last("a", "b", "c").reason().as_deref(), Some("c"));
 }
 
 #[test]
@@ -185,7 +177,7 @@ fn override_reason_from_struct_field() {
             a: "1",
             b: "2",
             c: "3",
-        }.reason(),
+        }.reason().as_deref(),
         Some("1")
     );
     assert_eq!(
@@ -193,7 +185,7 @@ fn override_reason_from_struct_field() {
             a: "1",
             b: "2",
             c: "3",
-        }.reason(),
+        }.reason().as_deref(),
         Some("2")
     );
     assert_eq!(
@@ -201,7 +193,7 @@ fn override_reason_from_struct_field() {
             a: "1",
             b: "2",
             c: "3",
-        }.reason(),
+        }.reason().as_deref(),
         Some("3")
     );
 }
@@ -318,3 +310,741 @@ fn override_status_code_from_struct_field() {
         StatusCode::FORBIDDEN
     );
 }
+
+#[test]
+fn to_response() {
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(status = 404, reason = "user not found")]
+        NotFound,
+        Other,
+    }
+
+    let res: Response<String> = Error::NotFound.to_response();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(res.body(), "user not found");
+
+    let res: Response<Vec<u8>> = Error::Other.to_response();
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(res.body(), b"Internal Server Error");
+}
+
+#[test]
+fn problem_details() {
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(
+            status = 403,
+            type = "https://example.com/probs/out-of-credit",
+            title = "You do not have enough credit.",
+            detail_field = "detail"
+        )]
+        OutOfCredit { detail: String },
+        #[response(status = 404, reason = "Not Found", detail = "user does not exist")]
+        NotFound,
+        #[response(detail_field = 0)]
+        Tuple(String),
+        Other,
+    }
+
+    let err = Error::OutOfCredit {
+        detail: String::from("Your current balance is 30, but that costs 50."),
+    };
+    assert_eq!(
+        err.problem_type(),
+        Some("https://example.com/probs/out-of-credit")
+    );
+    assert_eq!(err.title().as_deref(), Some("You do not have enough credit."));
+    assert_eq!(
+        err.detail().as_deref(),
+        Some("Your current balance is 30, but that costs 50.")
+    );
+
+    assert_eq!(Error::NotFound.problem_type(), None);
+    assert_eq!(Error::NotFound.title().as_deref(), Some("Not Found"));
+    assert_eq!(Error::NotFound.detail().as_deref(), Some("user does not exist"));
+    assert_eq!(Error::Tuple(String::from("a")).detail().as_deref(), Some("a"));
+
+    let problem = Error::Other.problem();
+    assert_eq!(problem, {
+        let mut problem = Problem::new(StatusCode::INTERNAL_SERVER_ERROR);
+        problem.title = Some(String::from("Internal Server Error"));
+        problem
+    });
+
+    let res: Response<String> = err.to_problem_response();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    assert_eq!(res.headers()["content-type"], "application/problem+json");
+    assert_eq!(
+        res.body(),
+        "{\"type\":\"https://example.com/probs/out-of-credit\",\
+         \"title\":\"You do not have enough credit.\",\"status\":403,\
+         \"detail\":\"Your current balance is 30, but that costs 50.\"}"
+    );
+
+    let problem = Error::NotFound.problem().with_instance("/users/\"42\"");
+    assert_eq!(
+        problem.to_json(),
+        "{\"title\":\"Not Found\",\"status\":404,\"detail\":\"user does not exist\",\
+         \"instance\":\"/users/\\\"42\\\"\"}"
+    );
+}
+
+#[test]
+fn derive_struct() {
+    #[derive(Debug, EnumResponse)]
+    #[response(status = 429, reason = "rate limited")]
+    struct RateLimited {
+        retry_after: u64,
+    }
+
+    #[derive(Debug, EnumResponse)]
+    #[response(status_field = "status", reason_field = "message")]
+    struct ApiError {
+        status: StatusCode,
+        message: String,
+    }
+
+    #[derive(Debug, EnumResponse)]
+    #[response(status_field = 0, reason_field = 1)]
+    struct Tuple(StatusCode, &'static str);
+
+    #[derive(Debug, EnumResponse)]
+    struct Unit;
+
+    let err = RateLimited { retry_after: 60 };
+    assert_eq!(err.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(err.reason().as_deref(), Some("rate limited"));
+
+    let err = ApiError {
+        status: StatusCode::CONFLICT,
+        message: String::from("already exists"),
+    };
+    assert_eq!(err.status(), StatusCode::CONFLICT);
+    assert_eq!(err.reason().as_deref(), Some("already exists"));
+
+    let err = Tuple(StatusCode::GONE, "gone");
+    assert_eq!(err.status(), StatusCode::GONE);
+    assert_eq!(err.reason().as_deref(), Some("gone"));
+
+    assert_eq!(Unit.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(Unit.reason().as_deref(), Some("Internal Server Error"));
+}
+
+#[test]
+fn enum_default_status_and_reason() {
+    use enum_response::Catalog;
+
+    #[derive(Debug, EnumResponse)]
+    #[response(default_status = 400, default_reason = "validation failed")]
+    enum Error {
+        MissingName,
+        #[response(reason = "email is invalid")]
+        InvalidEmail,
+        #[response(status = "CONFLICT")]
+        Duplicate,
+    }
+
+    assert_eq!(Error::MissingName.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(Error::MissingName.reason().as_deref(), Some("validation failed"));
+    assert_eq!(Error::InvalidEmail.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(Error::InvalidEmail.reason().as_deref(), Some("email is invalid"));
+    assert_eq!(Error::Duplicate.status(), StatusCode::CONFLICT);
+    assert_eq!(Error::Duplicate.reason().as_deref(), Some("validation failed"));
+
+    #[derive(Debug, EnumResponse)]
+    #[response(default_status = 400, default_reason = "bad {{input}}")]
+    enum Escaped {
+        Invalid,
+    }
+
+    assert_eq!(Escaped::Invalid.reason().as_deref(), Some("bad {input}"));
+    assert_eq!(Escaped::CATALOG[0].reason, Some("bad {input}"));
+}
+
+#[test]
+fn deny_fallback() {
+    #[derive(Debug, EnumResponse)]
+    #[response(deny_fallback)]
+    enum Error {
+        #[response(status = 404)]
+        NotFound,
+        #[response(status_field = 0)]
+        Upstream(StatusCode),
+    }
+
+    assert_eq!(Error::NotFound.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        Error::Upstream(StatusCode::BAD_GATEWAY).status(),
+        StatusCode::BAD_GATEWAY
+    );
+}
+
+#[test]
+fn transparent() {
+    #[derive(Debug, EnumResponse)]
+    enum DbError {
+        #[response(status = 404, reason = "row not found", detail = "no such row")]
+        NotFound,
+        #[response(status = 503)]
+        Unavailable,
+    }
+
+    #[derive(Debug, EnumResponse)]
+    #[response(transparent)]
+    struct Wrapper(DbError);
+
+    #[derive(Debug, EnumResponse)]
+    enum ApiError {
+        #[response(transparent)]
+        Db(DbError),
+        #[response(transparent)]
+        Boxed { inner: Box<DbError> },
+        #[response(transparent = "source", reason = "query failed")]
+        Context { context: String, source: DbError },
+        #[response(transparent = 1)]
+        Tuple(String, Wrapper),
+        #[response(status = 400)]
+        Other,
+    }
+
+    let err = ApiError::Db(DbError::NotFound);
+    assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    assert_eq!(err.reason().as_deref(), Some("row not found"));
+    assert_eq!(err.title().as_deref(), Some("row not found"));
+    assert_eq!(err.detail().as_deref(), Some("no such row"));
+
+    let err = ApiError::Boxed {
+        inner: Box::new(DbError::Unavailable),
+    };
+    assert_eq!(err.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(err.reason().as_deref(), Some("Service Unavailable"));
+
+    let err = ApiError::Context {
+        context: String::from("loading user"),
+        source: DbError::NotFound,
+    };
+    assert_eq!(err.status(), StatusCode::NOT_FOUND);
+    assert_eq!(err.reason().as_deref(), Some("query failed"));
+    assert_eq!(err.title().as_deref(), Some("query failed"));
+    assert_eq!(err.problem().title.as_deref(), Some("query failed"));
+    assert_eq!(err.detail().as_deref(), Some("no such row"));
+
+    let err = ApiError::Tuple(String::new(), Wrapper(DbError::Unavailable));
+    assert_eq!(err.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    assert_eq!(ApiError::Other.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(ApiError::Other.reason().as_deref(), Some("Bad Request"));
+}
+
+#[test]
+fn override_status_code_from_status_like_field() {
+    #[derive(Debug, Clone)]
+    struct UpstreamStatus(u16);
+
+    impl From<UpstreamStatus> for StatusCode {
+        fn from(status: UpstreamStatus) -> Self {
+            StatusCode::from_u16(status.0).unwrap_or(StatusCode::BAD_GATEWAY)
+        }
+    }
+
+    // not `Clone`, so it has to implement `ToStatusCode` itself
+    #[derive(Debug)]
+    struct ProxyStatus(u16);
+
+    impl ToStatusCode for ProxyStatus {
+        fn to_status_code(&self) -> Option<StatusCode> {
+            StatusCode::from_u16(self.0).ok()
+        }
+    }
+
+    #[derive(Debug, EnumResponse)]
+    enum Error<'a> {
+        #[response(status_field = 0)]
+        Raw(u16),
+        #[response(status_field = 0)]
+        Proxy(ProxyStatus),
+        #[response(status_field = "status")]
+        Borrowed { status: &'a StatusCode },
+        #[response(status_field = 0)]
+        Newtype(UpstreamStatus),
+        #[response(status_field = 0)]
+        Str(&'a str),
+    }
+
+    assert_eq!(Error::Raw(418).status(), StatusCode::IM_A_TEAPOT);
+    assert_eq!(Error::Raw(42).status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert_eq!(
+        Error::Borrowed {
+            status: &StatusCode::CONFLICT,
+        }.status(),
+        StatusCode::CONFLICT
+    );
+    assert_eq!(
+        Error::Newtype(UpstreamStatus(504)).status(),
+        StatusCode::GATEWAY_TIMEOUT
+    );
+    assert_eq!(Error::Str("429").status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(Error::Proxy(ProxyStatus(502)).status(), StatusCode::BAD_GATEWAY);
+}
+
+#[test]
+fn override_reason_from_display_field() {
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(reason_field = 0)]
+        Io(std::io::Error),
+        #[response(reason_field = "limit")]
+        Limit { limit: u32 },
+        #[response(reason_field = 0, detail_field = 1)]
+        Owned(String, std::num::ParseIntError),
+    }
+
+    let err = Error::Io(std::io::Error::other("disk full"));
+    assert_eq!(err.reason().as_deref(), Some("disk full"));
+    assert_eq!(Error::Limit { limit: 42 }.reason().as_deref(), Some("42"));
+
+    let err = Error::Owned(String::from("a"), "x".parse::<u8>().unwrap_err());
+    match err.reason() {
+        Some(Cow::Borrowed(reason)) => assert_eq!(reason, "a"),
+        reason => panic!("expected borrowed reason, got {:?}", reason),
+    }
+    assert_eq!(err.detail().as_deref(), Some("invalid digit found in string"));
+}
+
+#[test]
+fn format_reason() {
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(status = 404, reason = "user {id} not found")]
+        UserNotFound { id: u64, name: String },
+        #[response(status = 413, reason = "{0} exceeds limit of {1}")]
+        TooLarge(usize, usize),
+        #[response(reason = "{1:?} and {{literal}} braces", detail = "{0}: {0:>4}")]
+        Debug(u8, &'static str),
+        #[response(reason = "{{escaped}}")]
+        Escaped,
+    }
+
+    let err = Error::UserNotFound {
+        id: 42,
+        name: String::from("a"),
+    };
+    assert_eq!(err.reason().as_deref(), Some("user 42 not found"));
+    assert_eq!(
+        Error::TooLarge(2048, 1024).reason().as_deref(),
+        Some("2048 exceeds limit of 1024")
+    );
+    let err = Error::Debug(7, "x");
+    assert_eq!(err.reason().as_deref(), Some("\"x\" and {literal} braces"));
+    assert_eq!(err.detail().as_deref(), Some("7:    7"));
+    match Error::Escaped.reason() {
+        Some(Cow::Borrowed(reason)) => assert_eq!(reason, "{escaped}"),
+        reason => panic!("expected borrowed reason, got {:?}", reason),
+    }
+}
+
+#[test]
+fn response_headers() {
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(status = 401, header(name = "WWW-Authenticate", value = "Bearer"))]
+        Unauthorized,
+        #[response(
+            status = 405,
+            header(name = "Allow", value = "GET"),
+            header(name = "Allow", value = "HEAD")
+        )]
+        MethodNotAllowed,
+        #[response(status = 429, header(name = "Retry-After", value_field = "retry_after"))]
+        RateLimited { retry_after: u64 },
+        #[response(status = 503, header(name = "Retry-After", value_field = 0))]
+        Unavailable(String),
+        #[response(transparent, header(name = "x-wrapped", value = "1"))]
+        Wrapped(Inner),
+        NoHeaders,
+    }
+
+    #[derive(Debug, EnumResponse)]
+    #[response(status = 400, header(name = "x-inner", value = "1"))]
+    struct Inner;
+
+    let headers = Error::Unauthorized.headers();
+    assert_eq!(headers["www-authenticate"], "Bearer");
+    let headers = Error::MethodNotAllowed.headers();
+    assert_eq!(
+        headers.get_all("allow").iter().collect::<Vec<_>>(),
+        vec!["GET", "HEAD"]
+    );
+    let headers = Error::RateLimited { retry_after: 120 }.headers();
+    assert_eq!(headers["retry-after"], "120");
+    let headers = Error::Unavailable(String::from("60")).headers();
+    assert_eq!(headers["retry-after"], "60");
+    let headers = Error::Wrapped(Inner).headers();
+    assert_eq!(headers["x-inner"], "1");
+    assert_eq!(headers["x-wrapped"], "1");
+    assert!(Error::NoHeaders.headers().is_empty());
+
+    let res: Response<String> = Error::RateLimited { retry_after: 120 }.to_response();
+    assert_eq!(res.headers()["retry-after"], "120");
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+}
+
+#[test]
+fn retry_after() {
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(status = 429, retry_after_field = "retry_after")]
+        RateLimited { retry_after: Duration },
+        #[response(status = 503, retry_after_field = 0)]
+        Maintenance(SystemTime),
+        #[response(status = 503, retry_after_field = 0)]
+        Unavailable(Option<u64>),
+    }
+
+    let err = Error::RateLimited {
+        retry_after: Duration::from_millis(1500),
+    };
+    assert_eq!(err.headers()["retry-after"], "2");
+    let err = Error::Maintenance(UNIX_EPOCH + Duration::from_secs(784111777));
+    assert_eq!(err.headers()["retry-after"], "Sun, 06 Nov 1994 08:49:37 GMT");
+    assert_eq!(Error::Unavailable(Some(60)).headers()["retry-after"], "60");
+    assert!(Error::Unavailable(None).headers().is_empty());
+
+    let res: Response<String> = Error::Unavailable(Some(60)).to_response();
+    assert_eq!(res.headers()["retry-after"], "60");
+}
+
+#[test]
+fn www_authenticate_challenge() {
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(status = 401, challenge(scheme = "Basic", realm = "api"))]
+        MissingCredentials,
+        #[response(
+            status = 401,
+            challenge(
+                scheme = "Bearer",
+                realm = "api",
+                error = "invalid_token",
+                error_description_field = "description"
+            )
+        )]
+        InvalidToken { description: String },
+        #[response(
+            status = "UNAUTHORIZED",
+            challenge(scheme = "Basic"),
+            challenge(scheme = "Bearer", realm = "api")
+        )]
+        Multiple,
+    }
+
+    let headers = Error::MissingCredentials.headers();
+    assert_eq!(headers["www-authenticate"], "Basic realm=\"api\"");
+    let err = Error::InvalidToken {
+        description: String::from("token \"abc\" expired"),
+    };
+    assert_eq!(
+        err.headers()["www-authenticate"],
+        "Bearer realm=\"api\", error=\"invalid_token\", \
+         error_description=\"token \\\"abc\\\" expired\""
+    );
+    let headers = Error::Multiple.headers();
+    assert_eq!(
+        headers.get_all("www-authenticate").iter().collect::<Vec<_>>(),
+        vec!["Basic", "Bearer realm=\"api\""]
+    );
+
+    let challenge = enum_response::Challenge::new("Bearer")
+        .realm("api")
+        .param("scope", "read write");
+    assert_eq!(challenge.to_string(), "Bearer realm=\"api\", scope=\"read write\"");
+}
+
+#[test]
+fn redirect_location() {
+    #[derive(Debug, EnumResponse)]
+    enum Redirect {
+        #[response(status = 303, location_field = "url")]
+        SeeOther { url: String },
+        #[response(status = "PERMANENT_REDIRECT", location_field = 0)]
+        Moved(enum_response::Uri),
+        #[response(status = 302, header(name = "Location", value = "/login"))]
+        Login,
+        #[response(status = 304)]
+        NotModified,
+    }
+
+    let redirect = Redirect::SeeOther {
+        url: String::from("/users/42"),
+    };
+    assert_eq!(redirect.status(), StatusCode::SEE_OTHER);
+    assert_eq!(redirect.headers()["location"], "/users/42");
+    let redirect = Redirect::Moved("https://example.com/new".parse().unwrap());
+    assert_eq!(redirect.headers()["location"], "https://example.com/new");
+    assert_eq!(Redirect::Login.headers()["location"], "/login");
+    assert!(Redirect::NotModified.headers().is_empty());
+}
+
+#[test]
+fn success_responses() {
+    #[derive(Debug, EnumResponse)]
+    enum Outcome {
+        #[response(status = 201, body = "{{\"id\":{id}}}", location_field = "location")]
+        Created { id: u64, location: String },
+        #[response(status = 202)]
+        Accepted,
+        #[response(status = 200, body_field = 0)]
+        Ok(String),
+        #[response(status = 204, body = "ignored")]
+        NoContent,
+        #[response(status = 304, location_field = 0)]
+        NotModified(String),
+        #[response(status = 404)]
+        NotFound,
+    }
+
+    let outcome = Outcome::Created {
+        id: 42,
+        location: String::from("/users/42"),
+    };
+    assert_eq!(outcome.body().as_deref(), Some("{\"id\":42}"));
+    let res: Response<String> = outcome.to_response();
+    assert_eq!(res.status(), StatusCode::CREATED);
+    assert_eq!(res.headers()["location"], "/users/42");
+    assert_eq!(res.body(), "{\"id\":42}");
+
+    let res: Response<String> = Outcome::Accepted.to_response();
+    assert_eq!(res.body(), "Accepted");
+
+    let res: Response<String> = Outcome::Ok(String::from("hello")).to_response();
+    assert_eq!(res.body(), "hello");
+
+    let res: Response<String> = Outcome::NoContent.to_response();
+    assert_eq!(res.status(), StatusCode::NO_CONTENT);
+    assert_eq!(res.body(), "");
+    assert!(res.headers().get("content-type").is_none());
+
+    let res: Response<String> = Outcome::NotModified(String::from("/a")).to_response();
+    assert_eq!(res.body(), "");
+    assert!(res.headers().get("content-type").is_none());
+
+    let res: Response<String> = Outcome::NotFound.to_response();
+    assert_eq!(res.body(), "Not Found");
+    assert_eq!(Outcome::NotFound.body(), None);
+}
+
+#[test]
+fn content_negotiation() {
+    use enum_response::formatter::{self, Html, Json, Text};
+    use enum_response::{HeaderValue, ResponseFormatter};
+
+    #[derive(Debug, EnumResponse)]
+    enum Error {
+        #[response(status = 404, reason = "user {id} not found", detail = "<{id}>")]
+        NotFound { id: u64 },
+        #[response(status = 400, content_type = "text/html")]
+        Html,
+    }
+
+    #[derive(Debug, EnumResponse)]
+    #[response(content_type = "application/problem+json")]
+    enum ApiError {
+        #[response(status = 400)]
+        BadRequest,
+        #[response(status = 406, content_type = "text/plain")]
+        NotAcceptable,
+        #[response(status = 409, content_type = "application/json")]
+        Conflict,
+    }
+
+    #[derive(Debug, EnumResponse)]
+    enum Outcome {
+        #[response(status = 200, body_field = 0)]
+        Ok(String),
+        #[response(status = 201, body = "{{\"id\":1}}", content_type = "application/json")]
+        Created,
+    }
+
+    let err = Error::NotFound { id: 42 };
+    let accept = HeaderValue::from_static("text/html;q=0.5, application/json");
+    let res: Response<String> = err.to_negotiated_response(Some(&accept));
+    assert_eq!(res.headers()["content-type"], "application/problem+json");
+    assert_eq!(
+        res.body(),
+        r#"{"title":"user 42 not found","status":404,"detail":"<42>"}"#
+    );
+
+    let accept = HeaderValue::from_static("application/xml, text/*;q=0.8");
+    let res: Response<String> = err.to_negotiated_response(Some(&accept));
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(res.body(), "user 42 not found");
+
+    let accept = HeaderValue::from_static("text/html");
+    let res: Response<String> = err.to_negotiated_response(Some(&accept));
+    assert_eq!(res.headers()["content-type"], "text/html; charset=utf-8");
+    assert!(res.body().contains("<h1>user 42 not found</h1>"));
+    assert!(res.body().contains("<p>&lt;42&gt;</p>"));
+
+    let accept = HeaderValue::from_static("application/xml");
+    let res: Response<String> = err.to_negotiated_response(Some(&accept));
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+
+    let res: Response<String> = Error::Html.to_response();
+    assert_eq!(res.headers()["content-type"], "text/html; charset=utf-8");
+    let res: Response<String> = ApiError::BadRequest.to_negotiated_response(Some(&accept));
+    assert_eq!(res.headers()["content-type"], "application/problem+json");
+    let res: Response<String> = ApiError::NotAcceptable.to_response();
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+    let res: Response<String> = ApiError::Conflict.to_response();
+    assert_eq!(res.headers()["content-type"], "application/json");
+    assert_eq!(res.body(), r#"{"title":"Conflict","status":409}"#);
+
+    // bodies are only negotiated with their declared content type
+    let accept = HeaderValue::from_static("application/json");
+    let outcome = Outcome::Ok(String::from("hello"));
+    let res: Response<String> = outcome.to_negotiated_response(Some(&accept));
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(res.body(), "hello");
+    let accept = HeaderValue::from_static("text/html");
+    let res: Response<String> = Outcome::Created.to_negotiated_response(Some(&accept));
+    assert_eq!(res.headers()["content-type"], "application/json");
+    assert_eq!(res.body(), r#"{"id":1}"#);
+
+    let formatters = formatter::FORMATTERS;
+    let accept = HeaderValue::from_static("text/plain;q=0, */*;q=0.1");
+    let formatter = formatter::negotiate(Some(&accept), formatters).unwrap();
+    assert_eq!(formatter.media_types(), Json.media_types());
+    let accept = HeaderValue::from_static("*/*;q=0.1, text/*;q=0, text/html;q=0.5");
+    let formatter = formatter::negotiate(Some(&accept), formatters).unwrap();
+    assert_eq!(formatter.media_types(), Html.media_types());
+    let accept = HeaderValue::from_static("text/plain;q=0");
+    assert!(formatter::negotiate(Some(&accept), formatters).is_none());
+
+    let formatters: &[&dyn ResponseFormatter] = &[&Json, &Html, &Text];
+    let accept = HeaderValue::from_static("*/*");
+    let formatter = formatter::negotiate(Some(&accept), formatters).unwrap();
+    assert_eq!(formatter.media_types(), Json.media_types());
+}
+
+#[test]
+fn error_codes() {
+    use enum_response::formatter::Html;
+
+    #[derive(Debug, EnumResponse)]
+    #[response(rename_all = "SCREAMING_SNAKE_CASE")]
+    enum Error {
+        #[response(status = 404, reason = "user {0} not found")]
+        UserNotFound(u64),
+        #[response(status = 400, code = "E_INVALID")]
+        Invalid,
+        #[response(status = 502)]
+        HTTPUpstreamFailed,
+        #[response(transparent)]
+        Inner(Inner),
+    }
+
+    #[derive(Debug, EnumResponse)]
+    #[response(status = 409, code = "CONFLICT")]
+    struct Inner;
+
+    #[derive(Debug, EnumResponse)]
+    #[response(rename_all = "kebab-case")]
+    enum Kebab {
+        UserNotFound,
+    }
+
+    #[derive(Debug, EnumResponse)]
+    enum Uncoded {
+        NotFound,
+    }
+
+    assert_eq!(Error::UserNotFound(42).code(), Some("USER_NOT_FOUND"));
+    assert_eq!(Error::Invalid.code(), Some("E_INVALID"));
+    assert_eq!(Error::HTTPUpstreamFailed.code(), Some("HTTP_UPSTREAM_FAILED"));
+    assert_eq!(Error::Inner(Inner).code(), Some("CONFLICT"));
+    assert_eq!(Kebab::UserNotFound.code(), Some("user-not-found"));
+    assert_eq!(Uncoded::NotFound.code(), None);
+
+    let err = Error::UserNotFound(42);
+    let res: Response<String> = err.to_problem_response();
+    assert_eq!(
+        res.body(),
+        r#"{"title":"user 42 not found","status":404,"code":"USER_NOT_FOUND"}"#
+    );
+    let res: Response<String> = err.to_response();
+    assert_eq!(res.body(), "USER_NOT_FOUND: user 42 not found");
+    let res: Response<String> = err.to_formatted_response(&Html);
+    assert!(res.body().contains("<p><code>USER_NOT_FOUND</code></p>"));
+}
+
+#[test]
+fn catalog() {
+    use enum_response::{Catalog, Descriptor};
+
+    #[derive(Debug, EnumResponse)]
+    #[response(rename_all = "SCREAMING_SNAKE_CASE", default_status = 400)]
+    enum Error<'a> {
+        #[response(status = 404, reason = "not {{found}}")]
+        NotFound,
+        #[response(status = "CONFLICT", reason = "conflict with {0}")]
+        Conflict(&'a str),
+        #[response(status_field = 0, code = "UPSTREAM")]
+        Upstream(u16),
+        Invalid,
+        #[response(transparent)]
+        Inner(Inner),
+    }
+
+    #[derive(Debug, EnumResponse)]
+    #[response(status = 409)]
+    struct Inner;
+
+    assert_eq!(
+        Error::CATALOG,
+        &[
+            Descriptor {
+                variant: "NotFound",
+                status: Some(StatusCode::NOT_FOUND),
+                reason: Some("not {found}"),
+                code: Some("NOT_FOUND"),
+            },
+            Descriptor {
+                variant: "Conflict",
+                status: Some(StatusCode::CONFLICT),
+                reason: None,
+                code: Some("CONFLICT"),
+            },
+            Descriptor {
+                variant: "Upstream",
+                status: None,
+                reason: None,
+                code: Some("UPSTREAM"),
+            },
+            Descriptor {
+                variant: "Invalid",
+                status: Some(StatusCode::BAD_REQUEST),
+                reason: None,
+                code: Some("INVALID"),
+            },
+            Descriptor {
+                variant: "Inner",
+                status: None,
+                reason: None,
+                code: None,
+            },
+        ]
+    );
+    assert_eq!(
+        Inner::CATALOG,
+        &[Descriptor {
+            variant: "Inner",
+            status: Some(StatusCode::CONFLICT),
+            reason: None,
+            code: None,
+        }]
+    );
+}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+enum Error {
+    #[response(status = 401, challenge(scheme = "Bearer realm"))]
+    InvalidScheme,
+    #[response(status = 401, challenge(realm = "api"))]
+    MissingScheme,
+    #[response(status = 401, challenge(scheme = "Bearer", error = "a\nb"))]
+    InvalidValue,
+    #[response(status = 401, challenge(scheme = "Bearer", error_field = "b"))]
+    MissingField { a: String },
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+#[response(rename_all = "Title Case")]
+enum Error {
+    #[response(status = 400, code = "")]
+    Empty,
+    #[response(status = 400, rename_all = "snake_case")]
+    Misplaced,
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+#[response(content_type = "json")]
+enum Error {
+    #[response(status = 400, content_type = "text/html/x")]
+    BadRequest,
+    #[response(status = 400, content_type = 1)]
+    NotAString,
+    #[response(status = 400, content_type = "application/xml")]
+    Unsupported,
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+#[response(deny_fallback)]
+enum Error {
+    #[response(status = 404)]
+    NotFound,
+    #[response(reason = "forgot the status")]
+    Forgotten,
+    Unit(String),
+}
+
+#[derive(EnumResponse)]
+#[response(deny_fallback, default_status = 400)]
+enum Defaulted {
+    Unit,
+}
+
+fn main() {}
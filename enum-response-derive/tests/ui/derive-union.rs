@@ -2,5 +2,8 @@
 extern crate enum_response_derive;
 
 #[derive(EnumResponse)]
-struct Test;
+union Test {
+    a: u16,
+}
 
+fn main() {}
@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+#[response(rename_all = "SCREAMING_SNAKE_CASE")]
+enum Error {
+    #[response(status = 404)]
+    NotFound,
+    #[response(status = 404, code = "NOT_FOUND")]
+    Missing,
+    #[response(status = 410, code = "NOT_FOUND")]
+    Gone,
+    #[response(status = 400, code = "INVALID")]
+    Invalid,
+    #[response(status = 422, code = "INVALID")]
+    Unprocessable,
+}
+
+fn main() {}
@@ -0,0 +1,22 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+enum Error {
+    #[response(reason = "user {id} not found")]
+    Struct { name: String },
+    #[response(reason = "{1} and {name}")]
+    Tuple(String),
+    #[response(reason = "{0}")]
+    Unit,
+    #[response(detail = "unterminated {")]
+    Unterminated(String),
+}
+
+#[derive(EnumResponse)]
+#[response(default_reason = "invalid {input}")]
+enum Default {
+    Input { input: String },
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+enum Error {
+    #[response(status = 401, header(name = "invalid name", value = "Bearer"))]
+    InvalidName,
+    #[response(status = 401, header(name = "WWW-Authenticate", value = "a\nb"))]
+    InvalidValue,
+    #[response(status = 400, header(name = "X-Note", value = "café"))]
+    NonAscii,
+    #[response(status = 429, header(name = "Retry-After"))]
+    MissingValue,
+    #[response(status = 429, header(name = "Retry-After", value_field = "b"))]
+    MissingField { a: u64 },
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(Debug, EnumResponse)]
+#[response(actix)]
+enum Error {
+    #[response(status = 400)]
+    BadRequest,
+}
+
+#[derive(Debug, EnumResponse)]
+#[response(axum, status = 403)]
+struct Forbidden;
+
+#[derive(Debug, EnumResponse)]
+#[response(rocket)]
+enum NotFound {
+    #[response(status = 404)]
+    Order,
+}
+
+#[derive(Debug, EnumResponse)]
+#[response(warp, status = 400)]
+struct Rejected;
+
+fn main() {}
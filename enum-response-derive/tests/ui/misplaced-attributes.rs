@@ -0,0 +1,17 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+#[response(status = 400)]
+enum Error {
+    #[response(default_status = 400)]
+    Unit,
+    #[response(status = 400, axum)]
+    Axum,
+}
+
+#[derive(EnumResponse)]
+#[response(default_reason = "a")]
+struct Struct;
+
+fn main() {}
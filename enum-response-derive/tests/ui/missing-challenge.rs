@@ -0,0 +1,17 @@
+#![deny(deprecated)]
+
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+enum Error {
+    #[response(status = 401)]
+    Unauthorized,
+    #[response(status = 401, header(name = "WWW-Authenticate", value = "Basic"))]
+    Header,
+    #[response(status = 401, allow_missing_challenge)]
+    OptOut,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+enum Error {
+    #[response(status = 1000)]
+    InvalidStatus,
+    #[response(unknown = "a", reason = 1)]
+    Unknown,
+    #[response(status_field = "b")]
+    Struct { a: u16 },
+    #[response]
+    Empty,
+}
+
+fn main() {}
@@ -7,3 +7,4 @@ enum Error {
     Struct { s: String }
 }
 
+fn main() {}
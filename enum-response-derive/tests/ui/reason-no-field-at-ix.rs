@@ -7,3 +7,4 @@ enum Error {
     Tuple(String)
 }
 
+fn main() {}
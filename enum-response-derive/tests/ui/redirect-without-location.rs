@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+enum Redirect {
+    #[response(status = 303)]
+    SeeOther { url: String },
+    #[response(status = "TEMPORARY_REDIRECT")]
+    Temporary,
+    #[response(status = 304)]
+    NotModified,
+}
+
+fn main() {}
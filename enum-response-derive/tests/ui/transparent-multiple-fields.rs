@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate enum_response_derive;
+
+#[derive(EnumResponse)]
+enum Error {
+    #[response(transparent)]
+    Tuple(String, String),
+    #[response(transparent = "c")]
+    Struct { a: String, b: String },
+}
+
+fn main() {}
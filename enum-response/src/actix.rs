@@ -0,0 +1,51 @@
+//! Integration with [actix-web](https://docs.rs/actix-web), enabled with the `actix` feature.
+//!
+//! Types deriving `EnumResponse` with `#[response(actix)]` on the enum or struct implement
+//! `ResponseError`, so handlers can return them as error directly. `ResponseError` requires the
+//! types to also implement `Debug` and `Display`.
+
+use actix_web::http::header::{HeaderName, HeaderValue};
+pub use actix_web::http::StatusCode;
+pub use actix_web::{HttpResponse, ResponseError};
+
+use EnumResponse;
+
+/// Converts the status of `response` into the status type of actix-web, which uses a different
+/// version of the `http` crate.
+pub fn status_code<R: EnumResponse>(response: &R) -> StatusCode {
+    StatusCode::from_u16(response.status().as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Converts `response` into an actix-web response, including its headers and the body built by
+/// `to_response`.
+pub fn error_response<R: EnumResponse>(response: &R) -> HttpResponse {
+    let (parts, body) = response.to_response::<String>().into_parts();
+    let mut res = HttpResponse::build(status_code(response));
+    for (name, value) in &parts.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_str().as_bytes()),
+            HeaderValue::from_bytes(value.as_bytes()),
+        ) {
+            res.append_header((name, value));
+        }
+    }
+    res.body(body)
+}
+
+/// Implements `ResponseError` for a type deriving `EnumResponse` with `#[response(actix)]`.
+/// Invoked by the derive, so that the feature of this crate decides whether it is available.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_actix {
+    ([$($impl_generics:tt)*] [$($ty:tt)*] [$($where_clause:tt)*]) => {
+        impl $($impl_generics)* $crate::actix::ResponseError for $($ty)* $($where_clause)* {
+            fn status_code(&self) -> $crate::actix::StatusCode {
+                $crate::actix::status_code(self)
+            }
+
+            fn error_response(&self) -> $crate::actix::HttpResponse {
+                $crate::actix::error_response(self)
+            }
+        }
+    };
+}
@@ -0,0 +1,28 @@
+//! Integration with [axum](https://docs.rs/axum), enabled with the `axum` feature.
+//!
+//! Types deriving `EnumResponse` with `#[response(axum)]` on the enum or struct implement
+//! `IntoResponse`, so handlers can return them directly, e.g. as `Result<Json<User>, Error>`.
+
+use axum_lib::body::Body;
+pub use axum_lib::response::{IntoResponse, Response};
+
+use EnumResponse;
+
+/// Converts `response` into an axum response with the body built by `to_response`.
+pub fn into_response<R: EnumResponse>(response: &R) -> Response {
+    response.to_response::<String>().map(Body::from)
+}
+
+/// Implements `IntoResponse` for a type deriving `EnumResponse` with `#[response(axum)]`. Invoked
+/// by the derive, so that the feature of this crate decides whether it is available.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_axum {
+    ([$($impl_generics:tt)*] [$($ty:tt)*] [$($where_clause:tt)*]) => {
+        impl $($impl_generics)* $crate::axum::IntoResponse for $($ty)* $($where_clause)* {
+            fn into_response(self) -> $crate::axum::Response {
+                $crate::axum::into_response(&self)
+            }
+        }
+    };
+}
@@ -0,0 +1,44 @@
+use std::borrow::Cow;
+
+use http::StatusCode;
+
+use EnumResponse;
+
+/// The response metadata of a single variant that is known without constructing it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Descriptor {
+    /// The name of the variant, or of the struct.
+    pub variant: &'static str,
+    /// The status, or `None` if it is taken from a field or an inner `EnumResponse`.
+    pub status: Option<StatusCode>,
+    /// The reason, or `None` if it is formatted, taken from a field, from an inner
+    /// `EnumResponse` or if it is the canonical reason of the status.
+    pub reason: Option<&'static str>,
+    /// The code, or `None` if there is none or it is taken from an inner `EnumResponse`.
+    pub code: Option<&'static str>,
+}
+
+/// A static catalog of all variants of a type deriving `EnumResponse`, e.g. to generate docs or
+/// to check an API contract in tests.
+pub trait Catalog {
+    /// One descriptor per variant, in declaration order.
+    const CATALOG: &'static [Descriptor];
+}
+
+/// Renders a variant from its descriptor, e.g. for errors that are not raised by the application
+/// itself. Without a known status, `500 Internal Server Error` is used.
+impl EnumResponse for Descriptor {
+    fn status(&self) -> StatusCode {
+        self.status.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn reason(&self) -> Option<Cow<'_, str>> {
+        self.reason
+            .or_else(|| self.status().canonical_reason())
+            .map(Cow::Borrowed)
+    }
+
+    fn code(&self) -> Option<&str> {
+        self.code
+    }
+}
@@ -0,0 +1,56 @@
+use std::fmt;
+
+use http::HeaderValue;
+
+/// An authentication challenge as sent in the `WWW-Authenticate` header of a `401 Unauthorized`
+/// response, see [RFC 7235](https://www.rfc-editor.org/rfc/rfc7235#section-4.1).
+///
+/// Parameter values are always sent as quoted strings, e.g.
+/// `Bearer realm="api", error="invalid_token"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    scheme: String,
+    params: Vec<(String, String)>,
+}
+
+impl Challenge {
+    pub fn new<S: Into<String>>(scheme: S) -> Self {
+        Challenge {
+            scheme: scheme.into(),
+            params: Vec::new(),
+        }
+    }
+
+    pub fn realm<S: Into<String>>(self, realm: S) -> Self {
+        self.param("realm", realm)
+    }
+
+    pub fn param<N: Into<String>, V: Into<String>>(mut self, name: N, value: V) -> Self {
+        self.params.push((name.into(), value.into()));
+        self
+    }
+
+    /// Returns the challenge as header value, or `None` if it contains characters that are not
+    /// allowed in headers.
+    pub fn to_header_value(&self) -> Option<HeaderValue> {
+        HeaderValue::from_str(&self.to_string()).ok()
+    }
+}
+
+impl fmt::Display for Challenge {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.scheme)?;
+        for (i, (name, value)) in self.params.iter().enumerate() {
+            f.write_str(if i == 0 { " " } else { ", " })?;
+            write!(f, "{}=\"", name)?;
+            for c in value.chars() {
+                if c == '"' || c == '\\' {
+                    f.write_str("\\")?;
+                }
+                write!(f, "{}", c)?;
+            }
+            f.write_str("\"")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use std::borrow::Cow;
+
+use http::HeaderValue;
+
+use EnumResponse;
+
+/// Renders the body of the response for an `EnumResponse`.
+pub trait ResponseFormatter {
+    /// The media types this formatter can produce, used for content negotiation. The first one
+    /// is the preferred one.
+    fn media_types(&self) -> &[&'static str];
+
+    /// The `Content-Type` of the body rendered for `response`. If it is not a valid header value,
+    /// the response is sent without `Content-Type`.
+    fn content_type(&self, response: &dyn EnumResponse) -> &'static str;
+
+    /// Renders the body for `response`.
+    fn format(&self, response: &dyn EnumResponse) -> String;
+}
+
+/// Renders errors as `application/problem+json` document and successful responses with their
+/// body as `application/json`. The body is expected to already be JSON.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Json;
+
+/// Renders the body, or if there is none the reason prefixed with the code, as `text/plain`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Text;
+
+/// Renders a minimal `text/html` page containing the title, code, detail and body.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Html;
+
+/// The built-in formatters, in the order preferred when the client accepts any media type.
+pub const FORMATTERS: &[&dyn ResponseFormatter] = &[&Text, &Json, &Html];
+
+impl ResponseFormatter for Json {
+    fn media_types(&self) -> &[&'static str] {
+        &["application/json", "application/problem+json"]
+    }
+
+    fn content_type(&self, response: &dyn EnumResponse) -> &'static str {
+        if response.body().is_some() {
+            "application/json"
+        } else {
+            "application/problem+json"
+        }
+    }
+
+    fn format(&self, response: &dyn EnumResponse) -> String {
+        match response.body() {
+            Some(body) => body.into_owned(),
+            None => response.problem().to_json(),
+        }
+    }
+}
+
+impl ResponseFormatter for Text {
+    fn media_types(&self) -> &[&'static str] {
+        &["text/plain"]
+    }
+
+    fn content_type(&self, _: &dyn EnumResponse) -> &'static str {
+        "text/plain; charset=utf-8"
+    }
+
+    fn format(&self, response: &dyn EnumResponse) -> String {
+        if let Some(body) = response.body() {
+            return body.into_owned();
+        }
+        let reason = response.reason().map(Cow::into_owned).unwrap_or_default();
+        match response.code() {
+            Some(code) => format!("{}: {}", code, reason),
+            None => reason,
+        }
+    }
+}
+
+impl ResponseFormatter for Html {
+    fn media_types(&self) -> &[&'static str] {
+        &["text/html"]
+    }
+
+    fn content_type(&self, _: &dyn EnumResponse) -> &'static str {
+        "text/html; charset=utf-8"
+    }
+
+    fn format(&self, response: &dyn EnumResponse) -> String {
+        let title = response
+            .title()
+            .map(Cow::into_owned)
+            .unwrap_or_else(|| response.status().to_string());
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head><title>");
+        write_escaped(&mut html, &title);
+        html.push_str("</title></head>\n<body>\n<h1>");
+        write_escaped(&mut html, &title);
+        html.push_str("</h1>\n");
+        if let Some(code) = response.code() {
+            html.push_str("<p><code>");
+            write_escaped(&mut html, code);
+            html.push_str("</code></p>\n");
+        }
+        if let Some(detail) = response.detail() {
+            html.push_str("<p>");
+            write_escaped(&mut html, &detail);
+            html.push_str("</p>\n");
+        }
+        if let Some(body) = response.body() {
+            html.push_str("<pre>");
+            write_escaped(&mut html, &body);
+            html.push_str("</pre>\n");
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+fn write_escaped(html: &mut String, s: &str) {
+    for c in s.chars() {
+        match c {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+}
+
+/// Picks the formatter from `formatters` that best matches the `Accept` header of a request.
+///
+/// Each media type is weighted with the `q` parameter of the most specific media range matching
+/// it, so that e.g. `text/plain;q=0, */*` refuses `text/plain`. Media types are ranked by their
+/// weight and then by the order of their media range in the header, media types weighted with
+/// `q=0` are excluded. Without `Accept` header, the first formatter is used. Returns `None` if no
+/// formatter matches.
+pub fn negotiate<'a>(
+    accept: Option<&HeaderValue>,
+    formatters: &[&'a dyn ResponseFormatter],
+) -> Option<&'a dyn ResponseFormatter> {
+    let accept = match accept.and_then(|accept| accept.to_str().ok()) {
+        Some(accept) => accept,
+        None => return formatters.first().cloned(),
+    };
+
+    let ranges = accept
+        .split(',')
+        .map(|range| {
+            let mut parts = range.split(';');
+            let media_range = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+            let q = parts
+                .filter_map(|param| {
+                    let mut param = param.splitn(2, '=');
+                    match (param.next(), param.next()) {
+                        (Some(name), Some(value)) if name.trim() == "q" => {
+                            value.trim().parse().ok()
+                        }
+                        _ => None,
+                    }
+                })
+                .next()
+                .unwrap_or(1.0);
+            (media_range, q)
+        })
+        .collect::<Vec<(String, f32)>>();
+
+    let mut best: Option<(f32, usize, &'a dyn ResponseFormatter)> = None;
+    for formatter in formatters {
+        for media_type in formatter.media_types() {
+            let (ix, q) = match weight(&ranges, media_type) {
+                Some((ix, q)) if q > 0.0 => (ix, q),
+                _ => continue,
+            };
+            let better = match best {
+                Some((best_q, best_ix, _)) => q > best_q || (q == best_q && ix < best_ix),
+                None => true,
+            };
+            if better {
+                best = Some((q, ix, *formatter));
+            }
+        }
+    }
+    best.map(|(_, _, formatter)| formatter)
+}
+
+/// Selects the formatter for the exact `media_type`, e.g. one set with
+/// `#[response(content_type = ...)]`.
+pub fn find<'a>(
+    media_type: &str,
+    formatters: &[&'a dyn ResponseFormatter],
+) -> Option<&'a dyn ResponseFormatter> {
+    let media_type = media_type.split(';').next().unwrap_or("").trim();
+    formatters
+        .iter()
+        .find(|formatter| {
+            formatter
+                .media_types()
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(media_type))
+        })
+        .cloned()
+}
+
+/// The position and `q` parameter of the first of the most specific `ranges` matching
+/// `media_type`.
+fn weight(ranges: &[(String, f32)], media_type: &str) -> Option<(usize, f32)> {
+    let mut weight = None;
+    let mut specificity = 0;
+    for (ix, (range, q)) in ranges.iter().enumerate() {
+        match media_range_specificity(range, media_type) {
+            Some(s) if weight.is_none() || s > specificity => {
+                weight = Some((ix, *q));
+                specificity = s;
+            }
+            _ => {}
+        }
+    }
+    weight
+}
+
+/// How specifically `range` matches `media_type`, or `None` if it does not match it at all.
+fn media_range_specificity(range: &str, media_type: &str) -> Option<u8> {
+    if range == media_type {
+        return Some(2);
+    }
+    if range == "*/*" {
+        return Some(0);
+    }
+    match (range.find('/'), media_type.find('/')) {
+        (Some(a), Some(b)) if &range[a..] == "/*" && range[..a] == media_type[..b] => Some(1),
+        _ => None,
+    }
+}
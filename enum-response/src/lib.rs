@@ -1,10 +1,297 @@
+#[cfg(feature = "actix")]
+extern crate actix_web;
+#[cfg(feature = "axum")]
+extern crate axum as axum_lib;
 extern crate http;
+extern crate httpdate;
+#[cfg(feature = "tower")]
+#[macro_use]
+extern crate pin_project_lite;
+#[cfg(feature = "rocket")]
+extern crate rocket as rocket_lib;
+#[cfg(feature = "tower")]
+extern crate tower_layer;
+#[cfg(feature = "tower")]
+extern crate tower_service;
+#[cfg(feature = "warp")]
+extern crate warp as warp_lib;
 
-pub use http::StatusCode;
+#[cfg(feature = "actix")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+mod catalog;
+mod challenge;
+pub mod formatter;
+mod problem;
+mod retry_after;
+#[cfg(feature = "rocket")]
+pub mod rocket;
+#[cfg(feature = "tower")]
+pub mod tower;
+#[cfg(feature = "warp")]
+pub mod warp;
+
+use std::borrow::Cow;
+use std::convert::TryInto;
+
+use http::header::CONTENT_TYPE;
+
+pub use catalog::{Catalog, Descriptor};
+pub use challenge::Challenge;
+pub use formatter::ResponseFormatter;
+pub use http::header::{HeaderMap, HeaderName, HeaderValue};
+pub use http::{Response, StatusCode, Uri};
+pub use problem::Problem;
+pub use retry_after::RetryAfter;
 
 pub trait EnumResponse {
     fn status(&self) -> StatusCode;
-    fn reason(&self) -> Option<&str> {
-        self.status().canonical_reason()
+    fn reason(&self) -> Option<Cow<'_, str>> {
+        self.status().canonical_reason().map(Cow::Borrowed)
+    }
+
+    /// A URI reference identifying the problem type (the `type` member of a problem details
+    /// document).
+    fn problem_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// A short summary of the problem type. Defaults to the reason.
+    fn title(&self) -> Option<Cow<'_, str>> {
+        self.reason()
+    }
+
+    /// An explanation specific to this occurrence of the problem.
+    fn detail(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// The body of a successful response, e.g. the representation of a created resource. If
+    /// `None`, `to_response` uses the reason instead.
+    fn body(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    /// Additional headers to send along with the response, e.g. `WWW-Authenticate` or `Allow`.
+    fn headers(&self) -> HeaderMap {
+        HeaderMap::new()
+    }
+
+    /// A stable, machine-readable code identifying the error, e.g. `USER_NOT_FOUND`, that clients
+    /// can branch on instead of the human-readable reason.
+    fn code(&self) -> Option<&str> {
+        None
+    }
+
+    /// The media type set with `#[response(content_type = ...)]`. If set and one of the built-in
+    /// formatters renders it, it selects that formatter instead of the `Accept` header of the
+    /// request and is sent as the `Content-Type` of the response, e.g. `application/json` instead
+    /// of `application/problem+json` for errors.
+    fn content_type(&self) -> Option<&str> {
+        None
+    }
+
+    /// Builds the RFC 9457 problem details document for `self`.
+    fn problem(&self) -> Problem {
+        Problem {
+            problem_type: self.problem_type().map(String::from),
+            title: self.title().map(Cow::into_owned),
+            status: self.status(),
+            detail: self.detail().map(Cow::into_owned),
+            instance: None,
+            code: self.code().map(String::from),
+        }
+    }
+
+    /// Builds a complete `http::Response` with the status and headers of `self` and a plain text
+    /// body containing its body or, if there is none, its reason. If `self` has a
+    /// `content_type()`, the body is rendered by the matching built-in formatter instead. The body
+    /// type is pluggable and only needs to be constructible from a `String` (e.g. `String`,
+    /// `Vec<u8>`, `hyper::Body`, `bytes::Bytes`).
+    ///
+    /// Responses with a status that does not allow a body (1xx, 204 and 304) are sent without
+    /// body and `Content-Type`.
+    fn to_response<B>(&self) -> Response<B>
+    where
+        Self: Sized,
+        B: From<String>,
+    {
+        self.to_negotiated_response(None)
     }
+
+    /// Like `to_response`, but with an `application/problem+json` body generated from
+    /// `problem()`.
+    fn to_problem_response<B>(&self) -> Response<B>
+    where
+        Self: Sized,
+        B: From<String>,
+    {
+        self.to_formatted_response(&formatter::Json)
+    }
+
+    /// Like `to_response`, but with the body format picked from the built-in formatters based on
+    /// the `Accept` header of the request. Falls back to plain text if none is acceptable.
+    ///
+    /// A body is sent as is, so responses with a body only take part in the negotiation if they
+    /// declare its `content_type()`, and are sent as plain text otherwise.
+    fn to_negotiated_response<B>(&self, accept: Option<&HeaderValue>) -> Response<B>
+    where
+        Self: Sized,
+        B: From<String>,
+    {
+        let declared = self.content_type().and_then(|content_type| {
+            formatter::find(content_type, formatter::FORMATTERS)
+                .map(|formatter| (content_type, formatter))
+        });
+        let (content_type, formatter) = match declared {
+            Some((content_type, formatter)) => (Some(content_type), formatter),
+            None => {
+                let negotiated = match self.body() {
+                    Some(_) => None,
+                    None => formatter::negotiate(accept, formatter::FORMATTERS),
+                };
+                (None, negotiated.unwrap_or(&formatter::Text))
+            }
+        };
+        let mut res = self.to_formatted_response(formatter);
+        // send the declared content type, unless it only lacks the parameters of the formatter's
+        let rendered = formatter.content_type(self);
+        let content_type = content_type
+            .filter(|content_type| {
+                let media_type = rendered.split(';').next().unwrap_or("");
+                !media_type.eq_ignore_ascii_case(content_type)
+            })
+            .and_then(|content_type| HeaderValue::from_str(content_type).ok());
+        if let Some(content_type) = content_type {
+            if res.headers().contains_key(CONTENT_TYPE) {
+                res.headers_mut().insert(CONTENT_TYPE, content_type);
+            }
+        }
+        res
+    }
+
+    /// Like `to_response`, but with the body rendered by `formatter`.
+    fn to_formatted_response<B>(&self, formatter: &dyn ResponseFormatter) -> Response<B>
+    where
+        Self: Sized,
+        B: From<String>,
+    {
+        let status = self.status();
+        let has_body = !(status.is_informational()
+            || status == StatusCode::NO_CONTENT
+            || status == StatusCode::NOT_MODIFIED);
+        let mut res = Response::new(B::from(if has_body {
+            formatter.format(self)
+        } else {
+            String::new()
+        }));
+        *res.status_mut() = status;
+        if has_body {
+            if let Ok(content_type) = HeaderValue::from_str(formatter.content_type(self)) {
+                res.headers_mut().insert(CONTENT_TYPE, content_type);
+            }
+        }
+        res.headers_mut().extend(self.headers());
+        res
+    }
+}
+
+/// Conversion of a field selected with `#[response(status_field = ...)]` into a `StatusCode`.
+///
+/// Implemented for every `Clone` type that can be converted into a `StatusCode` via `Into` or
+/// `TryInto`, e.g. `StatusCode`, `&StatusCode`, `u16` or `&str`, as the conversion consumes a
+/// clone of the field. Types that are not `Clone` can implement it themselves. If the conversion
+/// fails, the derived `status()` falls back to `500 Internal Server Error`.
+pub trait ToStatusCode {
+    fn to_status_code(&self) -> Option<StatusCode>;
+}
+
+impl<T> ToStatusCode for T
+where
+    T: Clone + TryInto<StatusCode>,
+{
+    fn to_status_code(&self) -> Option<StatusCode> {
+        self.clone().try_into().ok()
+    }
+}
+
+#[doc(hidden)]
+pub mod __private {
+    //! Support for the derive, not public API.
+    //!
+    //! Fields used as reason, title, detail or header value are wrapped into `Reason` and converted with
+    //! `(&Reason(field)).to_reason()`. Method resolution prefers `StrReason`, which borrows the
+    //! field if it implements `AsRef<str>`, and only falls back to `DisplayReason`, which formats
+    //! the field, for all other `Display` types.
+
+    use std::borrow::Cow;
+    use std::fmt::Display;
+
+    pub struct Reason<'a, T: ?Sized + 'a>(pub &'a T);
+
+    impl<'a, T: ?Sized> Clone for Reason<'a, T> {
+        fn clone(&self) -> Self {
+            *self
+        }
+    }
+
+    impl<'a, T: ?Sized> Copy for Reason<'a, T> {}
+
+    pub trait StrReason<'a> {
+        fn to_reason(self) -> Cow<'a, str>;
+    }
+
+    impl<'a, T: AsRef<str> + ?Sized> StrReason<'a> for &Reason<'a, T> {
+        fn to_reason(self) -> Cow<'a, str> {
+            Cow::Borrowed(self.0.as_ref())
+        }
+    }
+
+    pub trait DisplayReason<'a> {
+        fn to_reason(self) -> Cow<'a, str>;
+    }
+
+    impl<'a, T: Display + ?Sized> DisplayReason<'a> for Reason<'a, T> {
+        fn to_reason(self) -> Cow<'a, str> {
+            Cow::Owned(self.0.to_string())
+        }
+    }
+}
+
+// Without their feature, the integrations requested with an attribute fail to compile.
+#[cfg(not(feature = "actix"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_actix {
+    ($($tokens:tt)*) => {
+        compile_error!("#[response(actix)] requires the `actix` feature of enum-response");
+    };
+}
+
+#[cfg(not(feature = "axum"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_axum {
+    ($($tokens:tt)*) => {
+        compile_error!("#[response(axum)] requires the `axum` feature of enum-response");
+    };
+}
+
+#[cfg(not(feature = "rocket"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_rocket {
+    ($($tokens:tt)*) => {
+        compile_error!("#[response(rocket)] requires the `rocket` feature of enum-response");
+    };
+}
+
+#[cfg(not(feature = "warp"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_warp {
+    ($($tokens:tt)*) => {
+        compile_error!("#[response(warp)] requires the `warp` feature of enum-response");
+    };
 }
@@ -0,0 +1,89 @@
+use http::StatusCode;
+
+/// A problem details document as described in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    /// A URI reference identifying the problem type. Serialized as `type`; when absent, the
+    /// problem type is `about:blank`.
+    pub problem_type: Option<String>,
+    /// A short, human-readable summary of the problem type.
+    pub title: Option<String>,
+    /// The HTTP status code of the response.
+    pub status: StatusCode,
+    /// A human-readable explanation specific to this occurrence of the problem.
+    pub detail: Option<String>,
+    /// A URI reference identifying this specific occurrence of the problem.
+    pub instance: Option<String>,
+    /// A machine-readable error code, serialized as `code` extension member.
+    pub code: Option<String>,
+}
+
+impl Problem {
+    pub fn new(status: StatusCode) -> Self {
+        Problem {
+            problem_type: None,
+            title: None,
+            status,
+            detail: None,
+            instance: None,
+            code: None,
+        }
+    }
+
+    pub fn with_instance<S: Into<String>>(mut self, instance: S) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Serializes the problem into an `application/problem+json` document.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{");
+        if let Some(ref problem_type) = self.problem_type {
+            write_member(&mut json, "type", problem_type);
+        }
+        if let Some(ref title) = self.title {
+            write_member(&mut json, "title", title);
+        }
+        if json.len() > 1 {
+            json.push(',');
+        }
+        json.push_str("\"status\":");
+        json.push_str(self.status.as_str());
+        if let Some(ref detail) = self.detail {
+            write_member(&mut json, "detail", detail);
+        }
+        if let Some(ref instance) = self.instance {
+            write_member(&mut json, "instance", instance);
+        }
+        if let Some(ref code) = self.code {
+            write_member(&mut json, "code", code);
+        }
+        json.push('}');
+        json
+    }
+}
+
+fn write_member(json: &mut String, name: &str, value: &str) {
+    if json.len() > 1 {
+        json.push(',');
+    }
+    write_str(json, name);
+    json.push(':');
+    write_str(json, value);
+}
+
+fn write_str(json: &mut String, s: &str) {
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
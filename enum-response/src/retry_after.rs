@@ -0,0 +1,44 @@
+use std::time::{Duration, SystemTime};
+
+use http::HeaderValue;
+
+/// Conversion of a field selected with `#[response(retry_after_field = ...)]` into the value of a
+/// `Retry-After` header.
+///
+/// Delays (`Duration` and seconds as `u64`) are sent as delta-seconds, rounded up so that
+/// clients never retry too early. Points in time (`SystemTime`) are sent as HTTP-date. `None`
+/// omits the header.
+pub trait RetryAfter {
+    fn retry_after(&self) -> Option<HeaderValue>;
+}
+
+impl RetryAfter for Duration {
+    fn retry_after(&self) -> Option<HeaderValue> {
+        let secs = self.as_secs() + u64::from(self.subsec_nanos() > 0);
+        Some(HeaderValue::from(secs))
+    }
+}
+
+impl RetryAfter for u64 {
+    fn retry_after(&self) -> Option<HeaderValue> {
+        Some(HeaderValue::from(*self))
+    }
+}
+
+impl RetryAfter for SystemTime {
+    fn retry_after(&self) -> Option<HeaderValue> {
+        HeaderValue::from_str(&httpdate::fmt_http_date(*self)).ok()
+    }
+}
+
+impl<T: RetryAfter> RetryAfter for Option<T> {
+    fn retry_after(&self) -> Option<HeaderValue> {
+        self.as_ref().and_then(RetryAfter::retry_after)
+    }
+}
+
+impl<T: RetryAfter + ?Sized> RetryAfter for &T {
+    fn retry_after(&self) -> Option<HeaderValue> {
+        (**self).retry_after()
+    }
+}
@@ -0,0 +1,98 @@
+//! Integration with [Rocket](https://rocket.rs), enabled with the `rocket` feature.
+//!
+//! Types deriving `EnumResponse` with `#[response(rocket)]` on the enum or struct implement
+//! `Responder`. The response format is negotiated with the `Accept` header of the request.
+//! `catchers` renders the errors raised by Rocket itself, e.g. `404 Not Found`, in the same format.
+
+use std::future::ready;
+use std::io::Cursor;
+
+use rocket_lib::catcher::{self, Catcher};
+use rocket_lib::http::{Header, Status};
+pub use rocket_lib::response::{self, Responder};
+pub use rocket_lib::Request;
+
+use {Catalog, Descriptor, EnumResponse, HeaderValue};
+
+/// Converts `response` into a Rocket response, including its headers, with the body format
+/// negotiated based on the `Accept` header of `request`.
+pub fn respond_to<'r, 'o: 'r, R: EnumResponse>(
+    response: &R,
+    request: &'r Request<'_>,
+) -> response::Result<'o> {
+    let accept = request
+        .headers()
+        .get_one("Accept")
+        .and_then(|accept| HeaderValue::from_str(accept).ok());
+    let (parts, body) = response
+        .to_negotiated_response::<String>(accept.as_ref())
+        .into_parts();
+    let mut res = response::Response::build();
+    res.status(Status::new(parts.status.as_u16()));
+    for (name, value) in &parts.headers {
+        if let Ok(value) = value.to_str() {
+            res.header_adjoin(Header::new(name.as_str().to_string(), value.to_string()));
+        }
+    }
+    res.sized_body(body.len(), Cursor::new(body));
+    Ok(res.finalize())
+}
+
+/// Builds a catcher for each error status in the catalog of `T`, rendering the descriptor of the
+/// first variant with that status. Register them to format the errors raised by Rocket itself
+/// like the ones returned by handlers.
+pub fn catchers<T: Catalog>() -> Vec<Catcher> {
+    let mut catchers = Vec::new();
+    let mut statuses = Vec::new();
+    for descriptor in T::CATALOG {
+        let status = match descriptor.status {
+            Some(status) if status.is_client_error() || status.is_server_error() => status,
+            _ => continue,
+        };
+        if !statuses.contains(&status) {
+            statuses.push(status);
+            catchers.push(Catcher::new(status.as_u16(), DescriptorCatcher(*descriptor)));
+        }
+    }
+    catchers
+}
+
+#[derive(Clone)]
+struct DescriptorCatcher(Descriptor);
+
+impl catcher::Handler for DescriptorCatcher {
+    fn handle<'r, 'life0, 'life1, 'async_trait>(
+        &'life0 self,
+        _: Status,
+        request: &'r Request<'life1>,
+    ) -> catcher::BoxFuture<'r>
+    where
+        'r: 'async_trait,
+        'life0: 'async_trait,
+        'life1: 'async_trait,
+        Self: 'async_trait,
+    {
+        Box::pin(ready(respond_to(&self.0, request)))
+    }
+}
+
+/// Implements `Responder` for a type deriving `EnumResponse` with `#[response(rocket)]`. Invoked by
+/// the derive, so that the feature of this crate decides whether it is available. The impl
+/// generics start with the two lifetimes of `Responder`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_rocket {
+    (
+        [$r:lifetime, $o:lifetime]
+        [$($impl_generics:tt)*] [$($ty:tt)*] [$($where_clause:tt)*]
+    ) => {
+        impl $($impl_generics)* $crate::rocket::Responder<$r, $o> for $($ty)* $($where_clause)* {
+            fn respond_to(
+                self,
+                request: &$r $crate::rocket::Request<'_>,
+            ) -> $crate::rocket::response::Result<$o> {
+                $crate::rocket::respond_to(&self, request)
+            }
+        }
+    };
+}
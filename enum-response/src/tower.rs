@@ -0,0 +1,183 @@
+//! Integration with [tower](https://docs.rs/tower), enabled with the `tower` feature.
+//!
+//! `ErrorResponseLayer` wraps a service whose `Error` implements `EnumResponse` and turns its
+//! errors into responses, so any tower based server renders them consistently:
+//!
+//! ```ignore
+//! let service = ServiceBuilder::new()
+//!     .layer(ErrorResponseLayer::new())
+//!     .service(service);
+//! ```
+
+use std::convert::Infallible;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use http::header::{HeaderValue, ACCEPT};
+use http::{Request, Response};
+pub use tower_layer::Layer;
+pub use tower_service::Service;
+
+use EnumResponse;
+
+/// Applies `ErrorResponse` to services. `E` is the error type of the wrapped services and is
+/// usually inferred.
+pub struct ErrorResponseLayer<E> {
+    error: PhantomData<fn() -> E>,
+}
+
+impl<E> ErrorResponseLayer<E> {
+    pub fn new() -> Self {
+        ErrorResponseLayer { error: PhantomData }
+    }
+}
+
+impl<E> Default for ErrorResponseLayer<E> {
+    fn default() -> Self {
+        ErrorResponseLayer::new()
+    }
+}
+
+impl<E> Clone for ErrorResponseLayer<E> {
+    fn clone(&self) -> Self {
+        ErrorResponseLayer::new()
+    }
+}
+
+impl<E> fmt::Debug for ErrorResponseLayer<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ErrorResponseLayer").finish()
+    }
+}
+
+impl<S, E> Layer<S> for ErrorResponseLayer<E> {
+    type Service = ErrorResponse<S, E>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ErrorResponse::new(inner)
+    }
+}
+
+/// Converts the errors of the wrapped service into responses, with the body format negotiated
+/// based on the `Accept` header of the request.
+///
+/// If the wrapped service fails in `poll_ready`, the next request is answered with that error
+/// instead, and the service is polled again for the requests after it. This suits services which
+/// report temporary failures like overload from `poll_ready`. Services that cannot recover from
+/// such a failure should be discarded by a layer between them and `ErrorResponse` instead. Clones
+/// do not share the error of a pending request.
+pub struct ErrorResponse<S, E> {
+    inner: S,
+    error: Option<E>,
+}
+
+impl<S, E> ErrorResponse<S, E> {
+    pub fn new(inner: S) -> Self {
+        ErrorResponse { inner, error: None }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Clone, E> Clone for ErrorResponse<S, E> {
+    fn clone(&self) -> Self {
+        ErrorResponse::new(self.inner.clone())
+    }
+}
+
+impl<S: fmt::Debug, E: fmt::Debug> fmt::Debug for ErrorResponse<S, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ErrorResponse")
+            .field("inner", &self.inner)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+impl<S, E, ReqBody, ResBody> Service<Request<ReqBody>> for ErrorResponse<S, E>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>, Error = E>,
+    E: EnumResponse,
+    ResBody: From<String>,
+{
+    type Response = Response<ResBody>;
+    type Error = Infallible;
+    type Future = ResponseFuture<S::Future, ResBody>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        if self.error.is_some() {
+            return Poll::Ready(Ok(()));
+        }
+        match self.inner.poll_ready(cx) {
+            Poll::Ready(Err(err)) => {
+                self.error = Some(err);
+                Poll::Ready(Ok(()))
+            }
+            poll => poll.map(|_| Ok(())),
+        }
+    }
+
+    fn call(&mut self, req: Request<ReqBody>) -> Self::Future {
+        let accept = req.headers().get(ACCEPT).cloned();
+        match self.error.take() {
+            Some(error) => ResponseFuture {
+                accept: None,
+                response: Some(error.to_negotiated_response(accept.as_ref())),
+                inner: None,
+            },
+            None => ResponseFuture {
+                accept,
+                response: None,
+                inner: Some(self.inner.call(req)),
+            },
+        }
+    }
+}
+
+pin_project! {
+    /// The response future of `ErrorResponse`, which never fails.
+    #[derive(Debug)]
+    pub struct ResponseFuture<F, B> {
+        accept: Option<HeaderValue>,
+        response: Option<Response<B>>,
+        #[pin]
+        inner: Option<F>,
+    }
+}
+
+impl<F, B, E> Future for ResponseFuture<F, B>
+where
+    F: Future<Output = Result<Response<B>, E>>,
+    E: EnumResponse,
+    B: From<String>,
+{
+    type Output = Result<Response<B>, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if let Some(response) = this.response.take() {
+            return Poll::Ready(Ok(response));
+        }
+        let inner = this
+            .inner
+            .as_mut()
+            .as_pin_mut()
+            .expect("ResponseFuture polled after completion");
+        match inner.poll(cx) {
+            Poll::Ready(Ok(response)) => Poll::Ready(Ok(response)),
+            Poll::Ready(Err(error)) => {
+                Poll::Ready(Ok(error.to_negotiated_response(this.accept.as_ref())))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
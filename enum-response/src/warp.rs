@@ -0,0 +1,83 @@
+//! Integration with [warp](https://docs.rs/warp), enabled with the `warp` feature.
+//!
+//! Types deriving `EnumResponse` with `#[response(warp)]` on the enum or struct implement
+//! `Reject`, so they can be turned into rejections with `warp::reject::custom`. `Recover` renders
+//! them again in a `recover` filter:
+//!
+//! ```ignore
+//! let recover = Recover::new().register::<ApiError>().register::<DbError>();
+//! let routes = routes.recover(move |rejection| recover.recover(rejection));
+//! ```
+
+use std::future::{ready, Ready};
+
+use warp_lib::http::header::{HeaderName, HeaderValue};
+use warp_lib::http::StatusCode;
+use warp_lib::hyper::Body;
+pub use warp_lib::reject::Reject;
+pub use warp_lib::reply::Response;
+use warp_lib::Rejection;
+
+use EnumResponse;
+
+/// Renders the `EnumResponse` types registered with `register` found in a rejection.
+#[derive(Clone, Default)]
+pub struct Recover {
+    renderers: Vec<fn(&Rejection) -> Option<Response>>,
+}
+
+impl Recover {
+    pub fn new() -> Self {
+        Recover::default()
+    }
+
+    /// Renders rejections caused by `T`.
+    pub fn register<T: EnumResponse + Reject>(mut self) -> Self {
+        self.renderers.push(render::<T>);
+        self
+    }
+
+    /// Renders `rejection` if it was caused by one of the registered types, and otherwise passes
+    /// it on to the next `recover` filter or warp's default rejection handling.
+    pub fn recover(&self, rejection: Rejection) -> Ready<Result<Response, Rejection>> {
+        let response = self
+            .renderers
+            .iter()
+            .filter_map(|render| render(&rejection))
+            .next();
+        ready(response.ok_or(rejection))
+    }
+}
+
+fn render<T: EnumResponse + Reject>(rejection: &Rejection) -> Option<Response> {
+    rejection.find::<T>().map(into_response)
+}
+
+/// Converts `response` into a warp response, including its headers and the body built by
+/// `to_response`. warp uses a different version of the `http` crate, so status and headers are
+/// converted from their raw representation.
+pub fn into_response<R: EnumResponse>(response: &R) -> Response {
+    let (parts, body) = response.to_response::<String>().into_parts();
+    let mut res = Response::new(Body::from(body));
+    *res.status_mut() =
+        StatusCode::from_u16(parts.status.as_u16()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    for (name, value) in &parts.headers {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_str().as_bytes()),
+            HeaderValue::from_bytes(value.as_bytes()),
+        ) {
+            res.headers_mut().append(name, value);
+        }
+    }
+    res
+}
+
+/// Implements `Reject` for a type deriving `EnumResponse` with `#[response(warp)]`. Invoked by
+/// the derive, so that the feature of this crate decides whether it is available.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __impl_warp {
+    ([$($impl_generics:tt)*] [$($ty:tt)*] [$($where_clause:tt)*]) => {
+        impl $($impl_generics)* $crate::warp::Reject for $($ty)* $($where_clause)* {}
+    };
+}
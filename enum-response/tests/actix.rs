@@ -0,0 +1,87 @@
+#![cfg(feature = "actix")]
+
+extern crate actix_web;
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+
+use std::fmt;
+use std::future::{ready, Ready};
+
+use actix_web::http::header::HeaderMap;
+use actix_web::http::StatusCode;
+use actix_web::rt::System;
+use actix_web::{test, web, App};
+
+#[derive(Debug, EnumResponse)]
+#[response(actix)]
+enum UploadError {
+    #[response(
+        status = 405,
+        header(name = "Allow", value = "PUT"),
+        header(name = "Allow", value = "POST")
+    )]
+    MethodNotAllowed,
+    #[response(
+        status = 413,
+        reason = "upload exceeds {limit} bytes",
+        header(name = "X-Upload-Limit", value_field = "limit")
+    )]
+    TooLarge { limit: usize },
+}
+
+impl fmt::Display for UploadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+fn upload(body: web::Bytes) -> Ready<Result<String, UploadError>> {
+    ready(if body.len() > 4 {
+        Err(UploadError::TooLarge { limit: 4 })
+    } else {
+        Ok(format!("{} bytes", body.len()))
+    })
+}
+
+fn method_not_allowed() -> Ready<Result<String, UploadError>> {
+    ready(Err(UploadError::MethodNotAllowed))
+}
+
+#[test]
+fn response_error() {
+    let system = System::new();
+    let app = system.block_on(test::init_service(
+        App::new()
+            .route("/upload", web::put().to(upload))
+            .route("/upload", web::route().to(method_not_allowed)),
+    ));
+    let send = |req: test::TestRequest| -> (StatusCode, HeaderMap, String) {
+        let res = system.block_on(test::call_service(&app, req.to_request()));
+        let status = res.status();
+        let headers = res.headers().clone();
+        let body = system.block_on(test::read_body(res));
+        (status, headers, String::from_utf8(body.to_vec()).unwrap())
+    };
+
+    let (status, _, body) = send(test::TestRequest::put().uri("/upload").set_payload("abc"));
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "3 bytes");
+
+    let (status, headers, body) =
+        send(test::TestRequest::put().uri("/upload").set_payload("abcdef"));
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(headers.get("x-upload-limit").unwrap(), "4");
+    assert_eq!(
+        headers.get("content-type").unwrap(),
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(body, "upload exceeds 4 bytes");
+
+    let (status, headers, _) = send(test::TestRequest::get().uri("/upload"));
+    assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+    assert_eq!(
+        headers.get_all("allow").collect::<Vec<_>>(),
+        vec!["PUT", "POST"]
+    );
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "axum")]
+
+extern crate axum;
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+
+mod support;
+
+use std::future::{ready, Ready};
+
+use axum::body::{to_bytes, Body};
+use axum::handler::Handler;
+use axum::http::{HeaderMap, Request, StatusCode};
+use support::block_on;
+
+#[derive(Debug, EnumResponse)]
+#[response(axum)]
+enum AuthError {
+    #[response(
+        status = 401,
+        challenge(scheme = "Bearer", realm = "api", error = "invalid_token")
+    )]
+    InvalidToken,
+    #[response(
+        status = 403,
+        reason = "missing scope `{0}`",
+        content_type = "application/problem+json"
+    )]
+    MissingScope(&'static str),
+}
+
+fn profile(headers: HeaderMap) -> Ready<Result<&'static str, AuthError>> {
+    ready(match headers.get("authorization").map(|auth| auth.as_bytes()) {
+        Some(b"Bearer admin") => Ok("alice"),
+        Some(b"Bearer guest") => Err(AuthError::MissingScope("profile")),
+        _ => Err(AuthError::InvalidToken),
+    })
+}
+
+fn get(authorization: &str) -> (StatusCode, HeaderMap, String) {
+    let req = Request::builder()
+        .header("Authorization", authorization)
+        .body(Body::empty())
+        .unwrap();
+    let res = block_on(Handler::<_, ()>::call(profile, req, ()));
+    let (parts, body) = res.into_parts();
+    let body = block_on(to_bytes(body, usize::MAX)).unwrap();
+    (
+        parts.status,
+        parts.headers,
+        String::from_utf8(body.to_vec()).unwrap(),
+    )
+}
+
+#[test]
+fn into_response() {
+    let (status, _, body) = get("Bearer admin");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, "alice");
+
+    let (status, headers, body) = get("Bearer expired");
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        headers["www-authenticate"],
+        r#"Bearer realm="api", error="invalid_token""#
+    );
+    assert_eq!(headers["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(body, "Unauthorized");
+
+    let (status, headers, body) = get("Bearer guest");
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(headers["content-type"], "application/problem+json");
+    assert_eq!(body, r#"{"title":"missing scope `profile`","status":403}"#);
+}
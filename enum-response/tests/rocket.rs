@@ -0,0 +1,76 @@
+#![cfg(feature = "rocket")]
+
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+extern crate rocket;
+
+use std::fmt::Display;
+use std::future::ready;
+
+use rocket::http::{Accept, Method, Status};
+use rocket::local::blocking::Client;
+use rocket::route::{BoxFuture, Outcome};
+use rocket::{Data, Request, Route};
+
+// the default of `Id` must not end up in the generated `Responder` impl
+#[derive(Debug, EnumResponse)]
+#[response(rocket, rename_all = "SCREAMING_SNAKE_CASE")]
+enum OrderError<Id: Display = u64> {
+    #[response(status = 404, reason = "order {0} not found")]
+    OrderNotFound(Id),
+    #[response(status = 422, reason = "order ids are numeric")]
+    InvalidId,
+}
+
+fn order<'r>(req: &'r Request<'_>, _: Data<'r>) -> BoxFuture<'r> {
+    let res = match req.param::<u64>(1) {
+        Some(Ok(1)) => Ok("order 1"),
+        Some(Ok(id)) => Err(OrderError::OrderNotFound(id)),
+        _ => Err(OrderError::InvalidId),
+    };
+    Box::pin(ready(Outcome::from(req, res)))
+}
+
+#[test]
+fn responder_and_catchers() {
+    let rocket = rocket::build()
+        .mount("/", vec![Route::new(Method::Get, "/orders/<id>", order)])
+        .register("/", enum_response::rocket::catchers::<OrderError>());
+    let client = Client::tracked(rocket).unwrap();
+
+    let res = client.get("/orders/1").dispatch();
+    assert_eq!(res.status(), Status::Ok);
+    assert_eq!(res.into_string().as_deref(), Some("order 1"));
+
+    let res = client.get("/orders/42").dispatch();
+    assert_eq!(res.status(), Status::NotFound);
+    assert_eq!(
+        res.headers().get_one("content-type"),
+        Some("text/plain; charset=utf-8")
+    );
+    assert_eq!(
+        res.into_string().as_deref(),
+        Some("ORDER_NOT_FOUND: order 42 not found")
+    );
+
+    let res = client.get("/orders/a").header(Accept::JSON).dispatch();
+    assert_eq!(res.status(), Status::UnprocessableEntity);
+    assert_eq!(
+        res.headers().get_one("content-type"),
+        Some("application/problem+json")
+    );
+    assert_eq!(
+        res.into_string().as_deref(),
+        Some(r#"{"title":"order ids are numeric","status":422,"code":"INVALID_ID"}"#)
+    );
+
+    // raised by Rocket itself and rendered by the catcher of the 404 variant
+    let res = client.get("/other").header(Accept::HTML).dispatch();
+    assert_eq!(res.status(), Status::NotFound);
+    assert_eq!(
+        res.headers().get_one("content-type"),
+        Some("text/html; charset=utf-8")
+    );
+    assert!(res.into_string().unwrap().contains("<h1>Not Found</h1>"));
+}
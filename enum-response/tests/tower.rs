@@ -0,0 +1,116 @@
+#![cfg(feature = "tower")]
+
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+extern crate http;
+
+mod support;
+
+use std::cell::Cell;
+use std::future::{poll_fn, ready, Ready};
+use std::rc::Rc;
+use std::task::{Context, Poll};
+
+use enum_response::tower::{ErrorResponseLayer, Layer, Service};
+use enum_response::{Response, StatusCode};
+use support::block_on;
+
+#[derive(Debug, EnumResponse)]
+#[response(rename_all = "snake_case")]
+enum GatewayError {
+    #[response(status = 502, reason = "upstream {0} failed")]
+    BadUpstream(String),
+    #[response(status = 503, retry_after_field = "retry_after")]
+    Overloaded { retry_after: u64 },
+}
+
+type Request = http::Request<()>;
+
+/// Proxies requests to the upstream named by their path, or fails in `poll_ready` if overloaded.
+#[derive(Default)]
+struct Gateway {
+    overloaded: Rc<Cell<bool>>,
+    polled: Rc<Cell<u64>>,
+}
+
+impl Service<Request> for Gateway {
+    type Response = Response<String>;
+    type Error = GatewayError;
+    type Future = Ready<Result<Response<String>, GatewayError>>;
+
+    fn poll_ready(&mut self, _: &mut Context) -> Poll<Result<(), GatewayError>> {
+        self.polled.set(self.polled.get() + 1);
+        Poll::Ready(if self.overloaded.get() {
+            Err(GatewayError::Overloaded {
+                retry_after: 60 * self.polled.get(),
+            })
+        } else {
+            Ok(())
+        })
+    }
+
+    fn call(&mut self, req: Request) -> Self::Future {
+        ready(match req.uri().path() {
+            "/users" => Ok(Response::new(String::from("alice"))),
+            path => Err(GatewayError::BadUpstream(path[1..].to_string())),
+        })
+    }
+}
+
+fn send<S: Service<Request>>(service: &mut S, path: &str, accept: Option<&str>) -> S::Response
+where
+    S::Error: std::fmt::Debug,
+{
+    let mut req = Request::builder().uri(path);
+    if let Some(accept) = accept {
+        req = req.header("Accept", accept);
+    }
+    block_on(poll_fn(|cx| service.poll_ready(cx))).unwrap();
+    block_on(service.call(req.body(()).unwrap())).unwrap()
+}
+
+#[test]
+fn error_response() {
+    let mut service = ErrorResponseLayer::new().layer(Gateway::default());
+
+    let res = send(&mut service, "/users", None);
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.body(), "alice");
+
+    let res = send(&mut service, "/orders", None);
+    assert_eq!(res.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(res.body(), "bad_upstream: upstream orders failed");
+
+    let res = send(&mut service, "/orders", Some("text/plain;q=0.5, application/json"));
+    assert_eq!(res.status(), StatusCode::BAD_GATEWAY);
+    assert_eq!(res.headers()["content-type"], "application/problem+json");
+    assert_eq!(
+        res.body(),
+        r#"{"title":"upstream orders failed","status":502,"code":"bad_upstream"}"#
+    );
+}
+
+#[test]
+fn error_response_poll_ready() {
+    let gateway = Gateway::default();
+    let overloaded = gateway.overloaded.clone();
+    let polled = gateway.polled.clone();
+    let mut service = ErrorResponseLayer::new().layer(gateway);
+
+    // each error of `poll_ready` answers a single request
+    overloaded.set(true);
+    for retry_after in &["60", "120"] {
+        let res = send(&mut service, "/users", Some("application/json"));
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(res.headers()["retry-after"], *retry_after);
+        assert_eq!(res.headers()["content-type"], "application/problem+json");
+    }
+    assert_eq!(polled.get(), 2);
+
+    overloaded.set(false);
+    let res = send(&mut service, "/users", None);
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(polled.get(), 3);
+}
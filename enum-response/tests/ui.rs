@@ -0,0 +1,12 @@
+extern crate trybuild;
+
+#[test]
+fn compile_test() {
+    let t = trybuild::TestCases::new();
+    if cfg!(feature = "axum") {
+        t.pass("tests/ui/axum-own-impl.rs");
+    }
+    if cfg!(feature = "rocket") {
+        t.pass("tests/ui/rocket-own-impl.rs");
+    }
+}
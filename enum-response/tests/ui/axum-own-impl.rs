@@ -0,0 +1,20 @@
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+
+use enum_response::axum::{into_response, IntoResponse, Response};
+
+// without #[response(axum)], enabling the feature does not conflict with an own impl
+#[derive(Debug, EnumResponse)]
+enum Error {
+    #[response(status = 404)]
+    NotFound,
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        into_response(&self)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+
+use enum_response::rocket::{respond_to, response, Request, Responder};
+
+// without #[response(rocket)], enabling the feature does not conflict with an own impl
+#[derive(Debug, EnumResponse)]
+enum Error {
+    #[response(status = 404)]
+    NotFound,
+}
+
+impl<'r, 'o: 'r> Responder<'r, 'o> for Error {
+    fn respond_to(self, request: &'r Request<'_>) -> response::Result<'o> {
+        respond_to(&self, request)
+    }
+}
+
+fn main() {}
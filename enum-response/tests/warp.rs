@@ -0,0 +1,79 @@
+#![cfg(feature = "warp")]
+
+extern crate enum_response;
+#[macro_use]
+extern crate enum_response_derive;
+extern crate warp;
+
+mod support;
+
+use std::future::ready;
+use std::time::Duration;
+
+use enum_response::warp::Recover;
+use support::block_on;
+use warp::http::StatusCode;
+use warp::{Filter, Rejection};
+
+#[derive(Debug, EnumResponse)]
+#[response(warp)]
+enum QuotaError {
+    #[response(
+        status = 429,
+        reason = "quota of {limit} requests exceeded",
+        retry_after_field = "reset"
+    )]
+    Exceeded { limit: u32, reset: Duration },
+}
+
+#[derive(Debug, EnumResponse)]
+#[response(warp, status = 503, header(name = "Retry-After", value = "120"))]
+struct Maintenance;
+
+#[derive(Debug, EnumResponse)]
+#[response(warp, status = 400)]
+struct Unregistered;
+
+fn quota(used: u32) -> Result<&'static str, Rejection> {
+    match used {
+        0..=9 => Ok("ok"),
+        10 => Err(warp::reject::custom(QuotaError::Exceeded {
+            limit: 10,
+            reset: Duration::from_millis(1500),
+        })),
+        11 => Err(warp::reject::custom(Unregistered)),
+        _ => Err(warp::reject::custom(Maintenance)),
+    }
+}
+
+#[test]
+fn recover() {
+    let recover = Recover::new()
+        .register::<QuotaError>()
+        .register::<Maintenance>();
+    let routes = warp::path!("quota" / u32)
+        .and_then(|used| ready(quota(used)))
+        .recover(move |rejection| recover.recover(rejection));
+    let get = |path: &str| block_on(warp::test::request().path(path).reply(&routes));
+
+    let res = get("/quota/1");
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.body(), "ok");
+
+    let res = get("/quota/10");
+    assert_eq!(res.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(res.headers()["retry-after"], "2");
+    assert_eq!(res.headers()["content-type"], "text/plain; charset=utf-8");
+    assert_eq!(res.body(), "quota of 10 requests exceeded");
+
+    let res = get("/quota/12");
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(res.headers()["retry-after"], "120");
+
+    // everything else is handled by warp itself
+    let res = get("/quota/11");
+    assert_eq!(res.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let res = get("/other");
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    assert_eq!(res.body(), "");
+}